@@ -1,38 +1,53 @@
+mod offline;
+mod validator;
+
+use kambo_hive::common::{SyntheticGraph, VERTEX_WEIGHTS_EXTENSION};
+#[cfg(feature = "sqlite")]
+use kambo_hive::host::sqlite_store::SqliteStore;
 use kambo_hive::host::{
-    periodic_saver,
+    best_known::BestKnownRegistry,
+    convergence,
+    experiment::Experiment,
+    export::{ExportFormat, ResultStream},
+    periodic_saver::PeriodicSaver,
     result_aggregator::ResultAggregator,
     server::start_server,
+    store::SharedStore,
+    tables,
     task_manager::{DistributionStrategy, TaskManager},
+    validator::ResultValidator,
 };
 use kambo_hive::utils::{init_logger, listen_for_workers};
+use kambo_hive_worker::graph::{Graph, generators};
 use log::{error, info, warn};
-use serde::{Deserialize, Serialize};
-use std::{env, fs, process, sync::Arc, time::Duration};
-use tokio::sync::Mutex;
-
-#[derive(Serialize, Deserialize)]
-pub struct GAConfig {
-    pub trials: u32,
-    pub max_stagnant: usize,
-    pub generations: usize,
-    pub tournament_size: usize,
-    pub crossover_probability: f32,
-    pub pop_size: Option<usize>,
-}
+use std::{collections::HashMap, env, fs, path::Path, process, sync::Arc, time::Duration};
+use tokio::sync::{Mutex, Notify};
+use validator::RomanDominationValidator;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     init_logger();
     let args: Vec<String> = env::args().collect();
 
+    match args.get(1).map(String::as_str) {
+        Some("tables") => return offline::tables(&args[2..]),
+        Some("merge") => return offline::merge(&args[2..]),
+        _ => {}
+    }
+
     if args.len() < 5 {
         eprintln!(
-            "Uso: {} <bind_addr:port> <graphs_path> <report_path> <strategy> [save_path] [save_interval_secs]",
+            "Uso: {} <bind_addr:port> <graphs_path> <report_path> <strategy> [save_path] [save_interval_secs] [experiment_path]",
+            args[0]
+        );
+        eprintln!("       {} tables <report_path> <experiment_path>", args[0]);
+        eprintln!(
+            "       {} merge [--update-best-known] <report_path> <experiment_path> <input>...",
             args[0]
         );
-        eprintln!("Estratégias disponíveis: fifo, lifo, random");
+        eprintln!("Estratégias disponíveis: fifo, lifo, random, largest, smallest");
         eprintln!(
-            "Exemplo: {} 0.0.0.0:12345 ./graphs final_report.json fifo results.json 60",
+            "Exemplo: {} 0.0.0.0:12345 ./graphs final_report.json fifo results.json 60 experiment.json",
             args[0]
         );
         process::exit(1);
@@ -44,14 +59,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let strategy_str = &args[4];
     let save_path = args.get(5);
     let save_interval = args.get(6).and_then(|s| s.parse().ok());
+    let experiment_path = args.get(7);
 
     let distribution_strategy = match strategy_str.to_lowercase().as_str() {
         "fifo" => DistributionStrategy::Fifo,
         "lifo" => DistributionStrategy::Lifo,
         "random" => DistributionStrategy::Random,
+        "largest" => DistributionStrategy::LargestFirst,
+        "smallest" => DistributionStrategy::SmallestFirst,
         _ => {
             error!(
-                "Estratégia de distribuição inválida: '{}'. Use 'fifo', 'lifo', ou 'random'.",
+                "Estratégia de distribuição inválida: '{}'. Use 'fifo', 'lifo', 'random', 'largest' ou 'smallest'.",
                 strategy_str
             );
             process::exit(1);
@@ -63,47 +81,208 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     let task_manager = Arc::new(Mutex::new(TaskManager::new(distribution_strategy)));
-    let result_aggregator = Arc::new(Mutex::new(ResultAggregator::new()));
-
-    let ga_config = GAConfig {
-        trials: 10,
-        max_stagnant: 100,
-        generations: 1000,
-        tournament_size: 2,
-        crossover_probability: 0.9,
-        pop_size: None,
+    let experiment = if let Some(path) = experiment_path {
+        info!("Lendo experimento de: {path}");
+        Experiment::from_file(path)?
+    } else {
+        warn!("Nenhum arquivo de experimento informado, usando o experimento padrão.");
+        Experiment::default()
+    };
+    info!(
+        "Experimento com {} execuções por grafo e algoritmos {:?}",
+        experiment.trials,
+        experiment
+            .algorithms
+            .iter()
+            .map(|a| format!("{} ({})", a.label(), a.problem))
+            .collect::<Vec<_>>()
+    );
+
+    let mut aggregator = ResultAggregator::with_objectives(experiment.objectives.clone());
+    aggregator.set_targets(experiment.targets.clone());
+    if let Some(path) = &experiment.best_known {
+        aggregator.set_best_known(BestKnownRegistry::load(path)?);
+    }
+    #[cfg(not(feature = "sqlite"))]
+    if experiment.database.is_some() {
+        return Err("O host foi compilado sem a feature 'sqlite' e não grava em banco.".into());
+    }
+    #[cfg(feature = "sqlite")]
+    let mut database = match &experiment.database {
+        Some(path) => Some(SqliteStore::open(
+            path,
+            experiment.name(),
+            &serde_json::to_string(&experiment)?,
+        )?),
+        None => None,
     };
-    let ag_config_str = serde_json::to_string(&ga_config)?;
+    // Os resultados gravados antes de um reinício entram no agregador antes das saídas
+    // serem abertas, para não serem acrescentados de novo a elas.
+    #[cfg(feature = "sqlite")]
+    if let Some(database) = &database {
+        let results = database.load_results()?;
+        info!("{} resultados recuperados do banco", results.len());
+        for result in results {
+            aggregator.add_result(result)?;
+        }
+    }
+    for export in &experiment.exports {
+        aggregator.add_stream(ResultStream::open(&export.path, export.format())?);
+    }
+    // Com extensão .csv ou .jsonl, save_path recebe cada resultado assim que ele chega
+    // em vez de ser reescrito periodicamente.
+    let save_format = save_path.map(|path| ExportFormat::from_path(Path::new(path)));
+    if let Some((path, format)) = save_path.zip(save_format)
+        && format.is_streaming()
+    {
+        aggregator.add_stream(ResultStream::open(path, format)?);
+    }
+    let result_aggregator = Arc::new(Mutex::new(aggregator));
+
+    let mut tm = task_manager.lock().await;
+    tm.set_objectives(experiment.objectives.clone());
+    tm.set_max_attempts(experiment.max_attempts);
+    // As instâncias geradas são gravadas junto dos outros grafos, para reprodução e
+    // validação, e entram nas tasks pela leitura do diretório abaixo.
+    if !experiment.generate.is_empty() {
+        fs::create_dir_all(graphs_path)?;
+    }
+    for spec in &experiment.generate {
+        for seed in spec.seeds() {
+            let file_name = spec.model.instance_name(seed);
+            let graph = generators::generate(&spec.model, seed);
+            let file_path = Path::new(graphs_path).join(&file_name);
+            graph
+                .save_edge_list(&file_path)
+                .map_err(|e| format!("Falha ao gravar '{}': {e}", file_path.display()))?;
+            info!(
+                "Instância sintética gerada: {file_name} ({} vértices)",
+                graph.get_num_vertices()
+            );
+            tm.add_synthetic_graph(
+                &file_name,
+                SyntheticGraph {
+                    model: spec.model.clone(),
+                    seed,
+                },
+            );
+        }
+    }
+
     info!("Lendo grafos de: {graphs_path}");
     let paths = fs::read_dir(graphs_path)?;
-    let mut tm = task_manager.lock().await;
+    // Formatos declarados no experimento, também usados pelo validador.
+    let mut graph_formats = HashMap::new();
     for path in paths {
         let path = path?.path();
-        if path.is_file() {
-            if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-                info!("Adicionando tasks para o grafo: {file_name}");
-                tm.add_new_graph_tasks(file_name, ga_config.trials, &ag_config_str);
+        // Arquivos de pesos acompanham os grafos e não são instâncias.
+        if path.is_file()
+            && path.extension().and_then(|e| e.to_str()) != Some(VERTEX_WEIGHTS_EXTENSION)
+            && let Some(file_name) = path.file_name().and_then(|n| n.to_str())
+        {
+            info!("Adicionando tasks para o grafo: {file_name}");
+            let format = experiment.graph_format(file_name);
+            if let Some(format) = format {
+                tm.set_graph_format(file_name, format);
+                graph_formats.insert(file_name.to_string(), format);
+            }
+            match Graph::load(&path.to_string_lossy(), format) {
+                Ok(graph) => tm.add_instance(file_name, graph.statistics()),
+                Err(e) => warn!("Sem estatísticas para '{file_name}': {e}"),
+            }
+            for algorithm in &experiment.algorithms {
+                let config = &algorithm.config;
+                if let Some(islands) = &algorithm.islands {
+                    tm.add_island_tasks(
+                        file_name,
+                        experiment.trials,
+                        &algorithm.name,
+                        algorithm.label(),
+                        algorithm.problem,
+                        config,
+                        islands,
+                    )?;
+                } else {
+                    tm.add_new_graph_tasks(
+                        file_name,
+                        experiment.trials,
+                        &algorithm.name,
+                        algorithm.label(),
+                        algorithm.problem,
+                        config,
+                    )?;
+                }
             }
         }
     }
+    #[cfg(feature = "sqlite")]
+    if let Some(database) = &mut database {
+        database.save_instances(tm.catalog())?;
+        let completed = database.completed_runs()?;
+        let skipped = tm.skip_tasks(|task| {
+            completed.contains(&(
+                task.graph_id.clone(),
+                task.configuration.clone(),
+                task.problem,
+                task.run_number,
+            ))
+        });
+        if skipped > 0 {
+            info!("{skipped} tarefas já concluídas em uma execução anterior foram puladas.");
+        }
+        database.save_tasks(tm.pending_tasks())?;
+    }
     let total_tasks = tm.get_total_tasks();
     info!("Total de {} tarefas adicionadas.", total_tasks);
     drop(tm);
 
+    #[cfg(feature = "sqlite")]
+    let store = database.map(|database| Arc::new(Mutex::new(database)) as SharedStore);
+    #[cfg(not(feature = "sqlite"))]
+    let store: Option<SharedStore> = None;
+
     let addr_clone = bind_addr.clone();
     tokio::spawn(async move {
         listen_for_workers(addr_clone).await;
     });
 
-    if let Some(path) = save_path {
-        let interval = save_interval.unwrap_or(300);
-        periodic_saver::start(Arc::clone(&result_aggregator), path.clone(), interval);
+    let saver = if save_format.is_some_and(ExportFormat::is_streaming) {
+        info!("Salvamento periódico substituído pela gravação de cada resultado.");
+        None
+    } else if let Some(path) = save_path {
+        let saver = PeriodicSaver::new(
+            path,
+            experiment.keep_saves,
+            Arc::clone(&task_manager),
+            Arc::clone(&result_aggregator),
+        );
+        let periodic = saver.start(save_interval.unwrap_or(300));
+        Some((saver, periodic))
     } else {
         warn!("Salvamento periódico desativado.");
-    }
+        None
+    };
+
+    let interrupted = Arc::new(Notify::new());
+    let ctrl_c = Arc::clone(&interrupted);
+    tokio::spawn(async move {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => ctrl_c.notify_one(),
+            Err(e) => error!("Falha ao escutar o Ctrl+C: {}", e),
+        }
+    });
 
     let server_task_manager = Arc::clone(&task_manager);
     let server_result_aggregator = Arc::clone(&result_aggregator);
+    let server_validator: Option<Arc<dyn ResultValidator>> = if experiment.validate_results {
+        info!("Validação de resultados ativada.");
+        Some(Arc::new(RomanDominationValidator::new(
+            graphs_path,
+            graph_formats,
+        )))
+    } else {
+        None
+    };
     let server_bind_addr = bind_addr.clone();
     tokio::spawn(async move {
         info!("Host TCP escutando em {}", server_bind_addr);
@@ -111,6 +290,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             &server_bind_addr,
             server_task_manager,
             server_result_aggregator,
+            server_validator,
+            store,
         )
         .await
         {
@@ -120,28 +301,65 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     });
 
     loop {
-        tokio::time::sleep(Duration::from_secs(5)).await;
+        tokio::select! {
+            () = tokio::time::sleep(Duration::from_secs(5)) => {}
+            () = interrupted.notified() => {
+                warn!("Interrupção recebida, salvando o progresso antes de encerrar...");
+                break;
+            }
+        }
 
         let tm_guard = task_manager.lock().await;
         let completed_count = tm_guard.get_completed_tasks_count();
+        let finished_count = tm_guard.get_finished_tasks_count();
 
         info!(
             "Progresso: {}/{} tarefas concluídas.",
             completed_count, total_tasks
         );
 
-        if completed_count >= total_tasks {
-            info!("Todas as tarefas foram concluídas!");
+        if finished_count >= total_tasks {
+            if finished_count > completed_count {
+                warn!(
+                    "{} tarefas falharam em todas as {} tentativas e ficaram sem resultado.",
+                    finished_count - completed_count,
+                    experiment.max_attempts
+                );
+            } else {
+                info!("Todas as tarefas foram concluídas!");
+            }
             let ra_guard = result_aggregator.lock().await;
             if let Err(e) = ra_guard.generate_and_save_report(&tm_guard, report_path) {
                 error!("Falha ao gerar o relatório final: {}", e);
             } else {
                 info!("Relatório final salvo com sucesso em '{}'", report_path);
             }
+            for spec in &experiment.tables {
+                if let Err(e) = tables::write(spec, ra_guard.statistics(), ra_guard.objectives()) {
+                    error!("Falha ao gerar a tabela '{}': {}", spec.path, e);
+                }
+            }
+            for spec in &experiment.convergence {
+                if let Err(e) = convergence::write(spec, ra_guard.statistics()) {
+                    error!(
+                        "Falha ao gerar as curvas de convergência '{}': {}",
+                        spec.path, e
+                    );
+                }
+            }
             break;
         }
     }
 
+    if let Some((saver, periodic)) = &saver {
+        // Um ciclo em andamento termina a escrita antes do salvamento final começar.
+        periodic.abort();
+        match saver.save().await {
+            Ok(count) => info!("Salvamento final com {} resultados concluído.", count),
+            Err(e) => error!("Falha no salvamento final: {}", e),
+        }
+    }
+
     info!("Encerrando o host...");
     Ok(())
 }
@@ -0,0 +1,106 @@
+use std::{error::Error, path::Path};
+
+use kambo_hive::host::{
+    best_known::BestKnownRegistry, convergence, experiment::Experiment, export, merge::ResultSet,
+    result_aggregator::ResultAggregator, tables,
+};
+use log::{info, warn};
+
+/// Flag do `merge` que permite gravar na tabela de melhores valores conhecidos os
+/// recordes encontrados nos resultados reunidos.
+const UPDATE_BEST_KNOWN: &str = "--update-best-known";
+
+/// Lê e reúne os resultados de `inputs` e reconstrói as estatísticas do experimento.
+/// A tabela de melhores valores conhecidos só é regravada com `update_best_known`.
+fn aggregate(
+    experiment: &Experiment,
+    inputs: &[String],
+    update_best_known: bool,
+) -> Result<(ResultSet, ResultAggregator), Box<dyn Error>> {
+    let mut set = ResultSet::new();
+    for input in inputs {
+        set.load(input, experiment.name())?;
+    }
+    if set.duplicates() > 0 {
+        info!(
+            "{} resultados repetidos (mesma task) foram ignorados",
+            set.duplicates()
+        );
+    }
+    info!(
+        "{} resultados de {} grafos reunidos",
+        set.results().len(),
+        set.graphs().len()
+    );
+
+    let mut aggregator = ResultAggregator::with_objectives(experiment.objectives.clone());
+    aggregator.set_targets(experiment.targets.clone());
+    if let Some(path) = &experiment.best_known {
+        let registry = if update_best_known {
+            BestKnownRegistry::load(path)?
+        } else {
+            BestKnownRegistry::load_read_only(path)?
+        };
+        aggregator.set_best_known(registry);
+    }
+    for result in set.results() {
+        aggregator.add_result(result.clone())?;
+    }
+    Ok((set, aggregator))
+}
+
+/// `tables <report_path> <experiment_path>`: gera as tabelas declaradas no experimento
+/// a partir de um relatório salvo, sem precisar do cluster.
+pub fn tables(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [report_path, experiment_path] = args else {
+        return Err("Uso: kambo-hive-host tables <report_path> <experiment_path>".into());
+    };
+    let experiment = Experiment::from_file(experiment_path)?;
+    if experiment.tables.is_empty() {
+        warn!("O experimento '{experiment_path}' não declara tabelas.");
+    }
+
+    let (_, aggregator) = aggregate(&experiment, std::slice::from_ref(report_path), false)?;
+    for spec in &experiment.tables {
+        tables::write(spec, aggregator.statistics(), aggregator.objectives())?;
+    }
+    for spec in &experiment.convergence {
+        convergence::write(spec, aggregator.statistics())?;
+    }
+    Ok(())
+}
+
+/// `merge [--update-best-known] <report_path> <experiment_path> <input>...`: reúne
+/// relatórios, arquivos de resultados e bancos de execuções separadas, ignorando tasks
+/// repetidas, e grava um novo relatório, as saídas, as tabelas e as curvas de
+/// convergência declaradas no experimento.
+pub fn merge(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (update_best_known, args) = match args.split_first() {
+        Some((flag, rest)) if flag == UPDATE_BEST_KNOWN => (true, rest),
+        _ => (false, args),
+    };
+    let [report_path, experiment_path, inputs @ ..] = args else {
+        return Err(format!(
+            "Uso: kambo-hive-host merge [{UPDATE_BEST_KNOWN}] <report_path> <experiment_path> <input>..."
+        )
+        .into());
+    };
+    if inputs.is_empty() {
+        return Err("Informe pelo menos um arquivo de resultados para reunir.".into());
+    }
+    let experiment = Experiment::from_file(experiment_path)?;
+
+    let (set, aggregator) = aggregate(&experiment, inputs, update_best_known)?;
+    aggregator.save_offline_report(set.catalog(), report_path)?;
+    for spec in &experiment.exports {
+        export::write_results(Path::new(&spec.path), spec.format(), set.results())?;
+        info!("Resultados reunidos gravados em '{}'", spec.path);
+    }
+    for spec in &experiment.tables {
+        tables::write(spec, aggregator.statistics(), aggregator.objectives())?;
+    }
+    for spec in &experiment.convergence {
+        convergence::write(spec, aggregator.statistics())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use kambo_hive::{
+    common::{GraphFormat, Problem, TaskResult},
+    host::validator::ResultValidator,
+};
+use kambo_hive_worker::{
+    graph::Graph,
+    variant::{self, RomanVariant},
+};
+use log::info;
+
+type Instance = (Arc<Graph>, Arc<dyn RomanVariant>);
+
+/// Recalcula o custo de cada solução e verifica se ela é viável para a variante
+/// de dominação romana da task.
+pub struct RomanDominationValidator {
+    graphs_path: String,
+    /// Formato dos grafos que o experimento fixa; os demais são detectados.
+    graph_formats: HashMap<String, GraphFormat>,
+    instances: Mutex<HashMap<(String, Problem), Instance>>,
+}
+
+impl RomanDominationValidator {
+    pub fn new(graphs_path: &str, graph_formats: HashMap<String, GraphFormat>) -> Self {
+        Self {
+            graphs_path: graphs_path.to_string(),
+            graph_formats,
+            instances: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Instância já carregada ou lida do disco. O cache não fica travado durante a
+    /// leitura, para que validações de outros grafos não esperem por ela.
+    fn instance(&self, graph_id: &str, problem: Problem) -> Result<Instance, String> {
+        let cached_graph = {
+            let instances = self.instances.lock().map_err(|e| e.to_string())?;
+            if let Some((graph, variant)) = instances.get(&(graph_id.to_string(), problem)) {
+                return Ok((Arc::clone(graph), Arc::clone(variant)));
+            }
+            instances
+                .iter()
+                .find(|((id, _), _)| id == graph_id)
+                .map(|(_, (graph, _))| Arc::clone(graph))
+        };
+
+        let graph_file_path = Path::new(&self.graphs_path).join(graph_id);
+        let graph = if let Some(graph) = cached_graph {
+            graph
+        } else {
+            info!(
+                "Carregando grafo para validação: {}",
+                graph_file_path.display()
+            );
+            let format = self.graph_formats.get(graph_id).copied();
+            let graph = Graph::load(&graph_file_path.to_string_lossy(), format)
+                .map_err(|e| format!("Falha ao carregar o grafo '{graph_id}': {e}"))?;
+            Arc::new(graph)
+        };
+        let variant: Arc<dyn RomanVariant> =
+            Arc::from(variant::load(problem, &graph, &graph_file_path)?);
+        let mut instances = self.instances.lock().map_err(|e| e.to_string())?;
+        let (graph, variant) = instances
+            .entry((graph_id.to_string(), problem))
+            .or_insert((graph, variant));
+        Ok((Arc::clone(graph), Arc::clone(variant)))
+    }
+}
+
+impl ResultValidator for RomanDominationValidator {
+    fn validate(&self, result: &TaskResult) -> Result<(), String> {
+        let (graph, variant) = self.instance(&result.graph_id, result.problem)?;
+        let labels = &result.solution_data;
+
+        if labels.len() != graph.get_num_vertices() {
+            return Err(format!(
+                "solução com {} rótulos para um grafo com {} vértices",
+                labels.len(),
+                graph.get_num_vertices()
+            ));
+        }
+        if let Some(&label) = labels.iter().find(|&&label| label > variant.max_label()) {
+            return Err(format!(
+                "rótulo {label} fora do alfabeto 0..={} do problema '{}'",
+                variant.max_label(),
+                result.problem
+            ));
+        }
+        let violations = variant.violations(&graph, labels);
+        if !violations.is_empty() {
+            return Err(format!(
+                "solução inviável para o problema '{}': {} vértices violam a regra (ex.: {:?})",
+                result.problem,
+                violations.len(),
+                &violations[..violations.len().min(5)]
+            ));
+        }
+
+        let cost = variant.cost(labels) as f64;
+        if (cost - result.fitness).abs() > f64::EPSILON {
+            return Err(format!(
+                "fitness informada {} difere do custo recalculado {}",
+                result.fitness, cost
+            ));
+        }
+        Ok(())
+    }
+}
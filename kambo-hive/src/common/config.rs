@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Configuração que acompanha uma `Task`, validada pelo worker ao decodificá-la.
+pub trait TaskConfig: Serialize + DeserializeOwned + Send + Sync + 'static {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Configuração usada quando a task não traz nenhuma (`null`); sem ela, `null`
+    /// é decodificado como qualquer outro valor.
+    fn omitted() -> Option<Self> {
+        None
+    }
+}
+
+/// Configuração sem esquema, para runners que aceitam qualquer JSON.
+impl TaskConfig for serde_json::Value {}
+
+/// Parâmetros do algoritmo genético compartilhados entre host e worker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GAConfig {
+    pub max_stagnant: usize,
+    pub generations: usize,
+    pub tournament_size: usize,
+    pub crossover_probability: f32,
+    pub pop_size: Option<usize>,
+    /// Aplica busca local em cada indivíduo gerado (AG memético).
+    #[serde(default)]
+    pub local_search: bool,
+    /// Tempo máximo de execução em segundos (AG e solver exato).
+    #[serde(default)]
+    pub time_limit_secs: Option<u64>,
+    /// Aplica as reduções seguras do grafo antes de resolver (apenas `roman`).
+    #[serde(default)]
+    pub reduce: bool,
+    /// Avalia os filhos de cada geração em outros workers com o avaliador de nome
+    /// dado (modo mestre-escravo), em vez de avaliá-los localmente.
+    #[serde(default)]
+    pub evaluator: Option<String>,
+    /// Registra a curva de convergência da execução com no máximo este número de pontos.
+    #[serde(default)]
+    pub trace_points: Option<usize>,
+}
+
+impl Default for GAConfig {
+    fn default() -> Self {
+        Self {
+            max_stagnant: 100,
+            generations: 1000,
+            tournament_size: 2,
+            crossover_probability: 0.9,
+            pop_size: None,
+            local_search: false,
+            time_limit_secs: None,
+            reduce: false,
+            evaluator: None,
+            trace_points: None,
+        }
+    }
+}
+
+impl TaskConfig for GAConfig {
+    fn omitted() -> Option<Self> {
+        Some(Self::default())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.generations == 0 {
+            return Err("generations deve ser maior que zero".to_string());
+        }
+        if self.tournament_size == 0 {
+            return Err("tournament_size deve ser maior que zero".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.crossover_probability) {
+            return Err(format!(
+                "crossover_probability deve estar em [0, 1], recebido {}",
+                self.crossover_probability
+            ));
+        }
+        if self.pop_size == Some(0) {
+            return Err("pop_size deve ser maior que zero".to_string());
+        }
+        if self.time_limit_secs == Some(0) {
+            return Err("time_limit_secs deve ser maior que zero".to_string());
+        }
+        if self.trace_points.is_some_and(|points| points < 2) {
+            return Err("trace_points deve ser pelo menos 2".to_string());
+        }
+        if self.reduce && self.evaluator.is_some() {
+            return Err("evaluator não pode ser combinado com reduce".to_string());
+        }
+        Ok(())
+    }
+}
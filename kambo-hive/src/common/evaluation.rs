@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{generator::SyntheticGraph, graph_format::GraphFormat, problem::Problem};
+
+/// Parte de um lote de avaliação (modo mestre-escravo): indivíduos de um
+/// coordenador a serem avaliados por outro worker com o avaliador `evaluator`.
+///
+/// Leva apenas o necessário para carregar a instância e as rotulações, sem a
+/// configuração do algoritmo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationJob {
+    pub id: Uuid,
+    pub batch_id: Uuid,
+    /// Posição do primeiro indivíduo deste job no lote.
+    pub offset: usize,
+    pub evaluator: String,
+    pub graph_id: String,
+    pub problem: Problem,
+    pub synthetic: Option<SyntheticGraph>,
+    #[serde(default)]
+    pub graph_format: Option<GraphFormat>,
+    pub individuals: Vec<Vec<u8>>,
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+/// Modelos de grafos sintéticos, com os parâmetros de cada um.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum GraphModel {
+    /// G(n, p): cada aresta existe com probabilidade `p`.
+    ErdosRenyi { n: usize, p: f64 },
+    /// Pontos uniformes no quadrado unitário, ligados se a distância for no máximo `radius`.
+    RandomGeometric { n: usize, radius: f64 },
+    /// Ligação preferencial: cada novo vértice se liga a `m` vértices, com chance proporcional ao grau.
+    BarabasiAlbert { n: usize, m: usize },
+    /// Grade `rows x cols` com vizinhança de 4.
+    Grid { rows: usize, cols: usize },
+    /// Árvore uniforme com `n` vértices (sequência de Prüfer).
+    Tree { n: usize },
+    /// G(n, p) com `cliques` cliques disjuntas de `clique_size` vértices plantadas.
+    PlantedCliques {
+        n: usize,
+        p: f64,
+        cliques: usize,
+        clique_size: usize,
+    },
+}
+
+impl GraphModel {
+    pub fn validate(&self) -> Result<(), String> {
+        let probability = |p: f64| {
+            if (0.0..=1.0).contains(&p) {
+                Ok(())
+            } else {
+                Err(format!("p deve estar em [0, 1], recebido {p}"))
+            }
+        };
+        match *self {
+            Self::ErdosRenyi { n, p } => {
+                positive("n", n)?;
+                probability(p)
+            }
+            Self::RandomGeometric { n, radius } => {
+                positive("n", n)?;
+                if radius < 0.0 {
+                    return Err(format!("radius deve ser não negativo, recebido {radius}"));
+                }
+                Ok(())
+            }
+            Self::BarabasiAlbert { n, m } => {
+                positive("m", m)?;
+                if n <= m {
+                    return Err(format!("n ({n}) deve ser maior que m ({m})"));
+                }
+                Ok(())
+            }
+            Self::Grid { rows, cols } => {
+                positive("rows", rows)?;
+                positive("cols", cols)
+            }
+            Self::Tree { n } => positive("n", n),
+            Self::PlantedCliques {
+                n,
+                p,
+                cliques,
+                clique_size,
+            } => {
+                positive("n", n)?;
+                probability(p)?;
+                if cliques * clique_size > n {
+                    return Err(format!(
+                        "{cliques} cliques de {clique_size} vértices não cabem em {n} vértices"
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Nome do arquivo da instância gerada com `seed`, que também é seu `graph_id`.
+    #[must_use]
+    pub fn instance_name(&self, seed: u64) -> String {
+        let parameters = match self {
+            Self::ErdosRenyi { n, p } => format!("erdos_renyi_n{n}_p{p}"),
+            Self::RandomGeometric { n, radius } => format!("random_geometric_n{n}_r{radius}"),
+            Self::BarabasiAlbert { n, m } => format!("barabasi_albert_n{n}_m{m}"),
+            Self::Grid { rows, cols } => format!("grid_{rows}x{cols}"),
+            Self::Tree { n } => format!("tree_n{n}"),
+            Self::PlantedCliques {
+                n,
+                p,
+                cliques,
+                clique_size,
+            } => format!("planted_cliques_n{n}_p{p}_k{cliques}x{clique_size}"),
+        };
+        format!("{parameters}_s{seed}.txt")
+    }
+}
+
+fn positive(name: &str, value: usize) -> Result<(), String> {
+    if value == 0 {
+        Err(format!("{name} deve ser maior que zero"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Grafo sintético de uma task: o worker o gera a partir do modelo e da semente
+/// em vez de ler um arquivo.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyntheticGraph {
+    #[serde(flatten)]
+    pub model: GraphModel,
+    pub seed: u64,
+}
@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Formatos de arquivo de grafo aceitos pelo worker. No experimento, `graph_format`
+/// e `graph_formats` fixam o formato quando a detecção não basta, como nas listas
+/// de arestas numeradas a partir de 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphFormat {
+    /// Um par `u v` por linha, vértices a partir de 0 (formato de `data/edges`).
+    EdgeList,
+    /// Um par `u v` por linha, vértices a partir de 1.
+    EdgeListOneBased,
+    /// DIMACS (`.col`): linhas `c` de comentário, `p edge n m` e `e u v`, vértices a partir de 1.
+    Dimacs,
+    /// METIS (`.graph`): cabeçalho `n m [fmt]` e a linha `i` lista os vizinhos do vértice `i`.
+    Metis,
+    /// Matrix Market (`.mtx`) em formato coordenado, vértices a partir de 1.
+    MatrixMarket,
+}
+
+impl GraphFormat {
+    #[must_use]
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "txt" | "edges" | "el" => Some(Self::EdgeList),
+            "col" | "dimacs" => Some(Self::Dimacs),
+            "graph" | "metis" => Some(Self::Metis),
+            "mtx" => Some(Self::MatrixMarket),
+            _ => None,
+        }
+    }
+
+    /// Detecta o formato pelo cabeçalho do arquivo e, se ele não for conclusivo, pela extensão.
+    #[must_use]
+    pub fn detect(path: &Path, first_line: &str) -> Self {
+        let first_line = first_line.trim_start();
+        if first_line.starts_with("%%MatrixMarket") {
+            Self::MatrixMarket
+        } else if first_line.starts_with("c ") || first_line.starts_with("p ") {
+            Self::Dimacs
+        } else {
+            Self::from_extension(path).unwrap_or(Self::EdgeList)
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+/// Estatísticas de uma instância, calculadas uma vez quando o grafo é carregado.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceStats {
+    pub vertices: usize,
+    pub edges: usize,
+    pub min_degree: usize,
+    pub max_degree: usize,
+    pub avg_degree: f64,
+    /// `degree_distribution[d]` é o número de vértices com grau `d`.
+    pub degree_distribution: Vec<usize>,
+    /// Fração das arestas possíveis presentes no grafo.
+    pub density: f64,
+    pub components: usize,
+    pub largest_component: usize,
+    pub isolated_vertices: usize,
+}
+
+impl InstanceStats {
+    /// Calcula as estatísticas a partir do grau de cada vértice e do tamanho de cada componente conexa.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_degrees(degrees: &[usize], component_sizes: &[usize]) -> Self {
+        let vertices = degrees.len();
+        let edges = degrees.iter().sum::<usize>() / 2;
+        let max_degree = degrees.iter().copied().max().unwrap_or(0);
+
+        let mut degree_distribution = vec![0; max_degree + 1];
+        for &degree in degrees {
+            degree_distribution[degree] += 1;
+        }
+
+        Self {
+            vertices,
+            edges,
+            min_degree: degrees.iter().copied().min().unwrap_or(0),
+            max_degree,
+            avg_degree: if vertices == 0 {
+                0.0
+            } else {
+                2.0 * edges as f64 / vertices as f64
+            },
+            isolated_vertices: degree_distribution[0],
+            degree_distribution,
+            density: if vertices < 2 {
+                0.0
+            } else {
+                2.0 * edges as f64 / (vertices as f64 * (vertices - 1) as f64)
+            },
+            components: component_sizes.len(),
+            largest_component: component_sizes.iter().copied().max().unwrap_or(0),
+        }
+    }
+
+    /// Tamanho usado como dica de escalonamento.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.vertices + self.edges
+    }
+}
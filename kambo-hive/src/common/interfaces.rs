@@ -1,7 +1,58 @@
 use uuid::Uuid;
 
-use super::{result::TaskResult, task::Task};
+use super::{
+    config::TaskConfig, evaluation::EvaluationJob, island::Migrant, problem::Problem,
+    result::TaskResult, task::Task,
+};
 
 pub trait GARunner: Send + Sync + 'static {
-    fn run(&self, task: Task, worker_id: Uuid) -> TaskResult;
+    type Config: TaskConfig;
+
+    /// Variantes do problema que o runner resolve. O worker as anuncia ao host, que
+    /// só lhe atribui tasks dessas variantes.
+    fn problems(&self) -> Vec<Problem> {
+        Problem::ALL.to_vec()
+    }
+
+    /// Verifica, antes da execução, se o runner executa `task` com `config`.
+    fn check(&self, task: &Task, config: &Self::Config) -> Result<(), String> {
+        let _ = (task, config);
+        Ok(())
+    }
+
+    fn run(&self, task: Task, config: Self::Config, worker_id: Uuid) -> Result<TaskResult, String>;
+
+    /// Executa a task com acesso ao host durante a execução, para trocar indivíduos
+    /// no modelo de ilhas (`Task::island`) ou distribuir avaliações. Runners que
+    /// não cooperam apenas executam `run`.
+    fn run_cooperative(
+        &self,
+        task: Task,
+        config: Self::Config,
+        worker_id: Uuid,
+        host: &mut dyn HostChannel,
+    ) -> Result<TaskResult, String> {
+        let _ = host;
+        self.run(task, config, worker_id)
+    }
+}
+
+/// Comunicação de uma task em execução com o host.
+pub trait HostChannel {
+    /// Envia os emigrantes da ilha e devolve os imigrantes recebidos desde a última troca.
+    fn exchange_migrants(&mut self, emigrants: Vec<Migrant>) -> Result<Vec<Migrant>, String>;
+
+    /// Avalia `individuals` nos workers que registraram `evaluator` e devolve os
+    /// fitness na mesma ordem.
+    fn evaluate_batch(
+        &mut self,
+        evaluator: &str,
+        individuals: Vec<Vec<u8>>,
+    ) -> Result<Vec<f64>, String>;
+}
+
+/// Função de avaliação executada pelos workers no modo mestre-escravo.
+pub trait FitnessEvaluator: Send + Sync + 'static {
+    /// Um fitness por indivíduo de `job`, na mesma ordem.
+    fn evaluate(&self, job: &EvaluationJob) -> Result<Vec<f64>, String>;
 }
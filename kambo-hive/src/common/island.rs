@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Para quais ilhas os emigrantes de uma ilha são enviados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topology {
+    /// A ilha `i` envia para a ilha `i + 1` (a última envia para a primeira).
+    #[default]
+    Ring,
+    /// Cada ilha envia para todas as outras.
+    FullyConnected,
+    /// A cada migração, uma outra ilha sorteada recebe os emigrantes.
+    Random,
+}
+
+/// Parâmetros do modelo de ilhas: cada execução de um algoritmo vira um grupo de
+/// `islands` tasks cooperativas que trocam indivíduos através do host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IslandModel {
+    pub islands: u32,
+    #[serde(default)]
+    pub topology: Topology,
+    /// Gerações entre duas migrações.
+    pub migration_interval: u32,
+    /// Quantos dos melhores indivíduos emigram a cada migração.
+    pub migrants: u32,
+}
+
+impl IslandModel {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.islands < 2 {
+            return Err(format!(
+                "o modelo de ilhas exige pelo menos duas ilhas, recebido {}",
+                self.islands
+            ));
+        }
+        if self.migration_interval == 0 {
+            return Err("migration_interval deve ser maior que zero".to_string());
+        }
+        if self.migrants == 0 {
+            return Err("migrants deve ser maior que zero".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Posição de uma task no seu grupo de ilhas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IslandTask {
+    pub group_id: Uuid,
+    pub island: u32,
+    pub model: IslandModel,
+}
+
+/// Indivíduo trocado entre ilhas: a rotulação do grafo resolvido e seu custo.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Migrant {
+    pub solution: Vec<u8>,
+    pub fitness: f64,
+}
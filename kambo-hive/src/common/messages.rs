@@ -1,13 +1,76 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{result::TaskResult, task::Task};
+use super::{
+    evaluation::EvaluationJob, island::Migrant, problem::Problem, result::TaskResult,
+    status::HostStatus, task::Task,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Request {
-    RequestTask { worker_id: Uuid },
-    ReportResult { worker_id: Uuid, result: TaskResult },
-    Heartbeat { worker_id: Uuid },
+    Register {
+        worker_id: Uuid,
+        algorithms: Vec<String>,
+        /// Variantes do problema que cada algoritmo resolve; um algoritmo ausente
+        /// resolve todas.
+        #[serde(default)]
+        problems: BTreeMap<String, Vec<Problem>>,
+        /// Avaliadores que o worker executa no modo mestre-escravo.
+        #[serde(default)]
+        evaluators: Vec<String>,
+    },
+    RequestTask {
+        worker_id: Uuid,
+    },
+    ReportResult {
+        worker_id: Uuid,
+        result: TaskResult,
+    },
+    ReportFailure {
+        worker_id: Uuid,
+        task_id: Uuid,
+        reason: String,
+    },
+    Heartbeat {
+        worker_id: Uuid,
+    },
+    /// Envia os emigrantes de uma ilha e pede os imigrantes destinados a ela.
+    Migrate {
+        worker_id: Uuid,
+        task_id: Uuid,
+        migrants: Vec<Migrant>,
+    },
+    /// Pede a avaliação de indivíduos da task `task_id` por outros workers.
+    SubmitBatch {
+        worker_id: Uuid,
+        task_id: Uuid,
+        evaluator: String,
+        individuals: Vec<Vec<u8>>,
+    },
+    /// Pede os fitness de um lote; o host espera um pouco pelo lote antes de responder.
+    PollBatch {
+        worker_id: Uuid,
+        batch_id: Uuid,
+    },
+    /// Avisa que o coordenador desistiu do lote e o avaliará localmente.
+    CancelBatch {
+        worker_id: Uuid,
+        batch_id: Uuid,
+    },
+    ReportEvaluation {
+        worker_id: Uuid,
+        job_id: Uuid,
+        fitnesses: Vec<f64>,
+    },
+    ReportEvaluationFailure {
+        worker_id: Uuid,
+        job_id: Uuid,
+        reason: String,
+    },
+    /// Consulta o andamento do experimento; não exige registro.
+    Status,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,10 +78,32 @@ pub enum Response {
     AssignTask {
         task: Task,
     },
+    /// Parte de um lote de avaliação; tem prioridade sobre as tasks.
+    AssignEvaluation {
+        job: EvaluationJob,
+    },
     NoTaskAvailable,
     Ack,
     Command {
         command_type: String,
         payload: String,
     },
+    Status {
+        status: HostStatus,
+    },
+    /// Imigrantes recebidos pela ilha desde a última migração.
+    Migrants {
+        migrants: Vec<Migrant>,
+    },
+    BatchAccepted {
+        batch_id: Uuid,
+    },
+    /// `None` enquanto o lote não foi totalmente avaliado.
+    BatchResults {
+        fitnesses: Option<Vec<f64>>,
+    },
+    /// Pedido recusado, por exemplo um lote sem workers capazes de avaliá-lo.
+    Rejected {
+        reason: String,
+    },
 }
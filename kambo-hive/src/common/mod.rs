@@ -1,9 +1,29 @@
+mod config;
+mod evaluation;
+mod generator;
+mod graph_format;
+mod instance;
 mod interfaces;
+mod island;
 mod messages;
+mod objective;
+mod problem;
 mod result;
+mod status;
 mod task;
+mod trace;
 
-pub use interfaces::GARunner;
+pub use config::{GAConfig, TaskConfig};
+pub use evaluation::EvaluationJob;
+pub use generator::{GraphModel, SyntheticGraph};
+pub use graph_format::GraphFormat;
+pub use instance::InstanceStats;
+pub use interfaces::{FitnessEvaluator, GARunner, HostChannel};
+pub use island::{IslandModel, IslandTask, Migrant, Topology};
 pub use messages::{Request, Response};
+pub use objective::{Objective, Objectives};
+pub use problem::{Problem, VERTEX_WEIGHTS_EXTENSION};
 pub use result::TaskResult;
+pub use status::HostStatus;
 pub use task::Task;
+pub use trace::{TracePoint, TraceRecorder};
@@ -0,0 +1,83 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::problem::Problem;
+
+/// Sentido de otimização da fitness de um problema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    /// Menor fitness é melhor, como em todas as variantes de dominação romana.
+    #[default]
+    Minimize,
+    Maximize,
+}
+
+impl Objective {
+    /// Ordena `a` antes de `b` quando `a` é melhor, de modo que `sort_by` deixa a
+    /// melhor fitness primeiro.
+    #[must_use]
+    pub fn compare(self, a: f64, b: f64) -> Ordering {
+        match self {
+            Self::Minimize => a.total_cmp(&b),
+            Self::Maximize => b.total_cmp(&a),
+        }
+    }
+
+    /// Indica se `candidate` é estritamente melhor que `current`.
+    #[must_use]
+    pub fn is_better(self, candidate: f64, current: f64) -> bool {
+        self.compare(candidate, current) == Ordering::Less
+    }
+
+    /// Melhor valor de `values`, ou `None` se estiver vazio.
+    pub fn best(self, values: impl IntoIterator<Item = f64>) -> Option<f64> {
+        values.into_iter().reduce(|best, value| {
+            if self.is_better(value, best) {
+                value
+            } else {
+                best
+            }
+        })
+    }
+
+    /// Limite mais apertado entre os informados pelos solvers: o maior limite inferior
+    /// ao minimizar e o menor limite superior ao maximizar.
+    pub fn tightest_bound(self, bounds: impl IntoIterator<Item = f64>) -> Option<f64> {
+        bounds.into_iter().reduce(match self {
+            Self::Minimize => f64::max,
+            Self::Maximize => f64::min,
+        })
+    }
+
+    /// Distância percentual entre `fitness` e o limite `bound`; `None` se o limite for zero.
+    #[must_use]
+    pub fn gap_percent(self, fitness: f64, bound: f64) -> Option<f64> {
+        if bound == 0.0 {
+            return None;
+        }
+        let gap = match self {
+            Self::Minimize => fitness - bound,
+            Self::Maximize => bound - fitness,
+        };
+        Some(gap / bound.abs() * 100.0)
+    }
+}
+
+/// Sentido de otimização declarado para cada variante do problema; as variantes não
+/// declaradas são minimizadas.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Objectives(BTreeMap<Problem, Objective>);
+
+impl Objectives {
+    #[must_use]
+    pub fn get(&self, problem: Problem) -> Objective {
+        self.0.get(&problem).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, problem: Problem, objective: Objective) {
+        self.0.insert(problem, objective);
+    }
+}
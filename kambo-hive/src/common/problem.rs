@@ -0,0 +1,50 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Extensão do arquivo com os pesos dos vértices de um grafo, usado por
+/// `Problem::WeightedRoman`: para `grafo.txt`, os pesos ficam em `grafo.txt.weights`.
+pub const VERTEX_WEIGHTS_EXTENSION: &str = "weights";
+
+/// Variante de dominação romana resolvida por uma task.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Problem {
+    /// Rótulos {0, 1, 2}; todo vértice com 0 tem um vizinho com 2.
+    #[default]
+    Roman,
+    /// Rótulos {0, 1, 2, 3}; todo vértice com 0 tem um vizinho com 3 ou dois com 2,
+    /// e todo vértice com 1 tem um vizinho com rótulo maior ou igual a 2.
+    DoubleRoman,
+    /// Dominação romana em que os vértices com rótulo positivo não ficam isolados entre si.
+    TotalRoman,
+    /// Dominação italiana (romana {2}): a soma dos rótulos dos vizinhos de um vértice com 0 é pelo menos 2.
+    Italian,
+    /// Dominação romana com custo igual à soma de peso do vértice vezes rótulo.
+    WeightedRoman,
+}
+
+impl Problem {
+    pub const ALL: [Self; 5] = [
+        Self::Roman,
+        Self::DoubleRoman,
+        Self::TotalRoman,
+        Self::Italian,
+        Self::WeightedRoman,
+    ];
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Roman => "roman",
+            Self::DoubleRoman => "double_roman",
+            Self::TotalRoman => "total_roman",
+            Self::Italian => "italian",
+            Self::WeightedRoman => "weighted_roman",
+        };
+        f.write_str(name)
+    }
+}
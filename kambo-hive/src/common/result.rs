@@ -1,13 +1,46 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{problem::Problem, trace::TracePoint};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskResult {
     pub task_id: Uuid,
     pub graph_id: String,
+    #[serde(default)]
+    pub algorithm: String,
+    /// Copiado de `Task::configuration`.
+    #[serde(default)]
+    pub configuration: String,
+    #[serde(default)]
+    pub problem: Problem,
+    /// Semente da task que produziu o resultado.
+    #[serde(default)]
+    pub seed: u64,
     pub worker_id: Uuid,
     pub fitness: f64,
     pub solution_data: Vec<u8>,
     pub interations_run: u32,
     pub processing_time_ms: u64,
+    /// Limite inferior conhecido para o grafo, usado no cálculo do gap.
+    #[serde(default)]
+    pub lower_bound: Option<f64>,
+    /// Indica que `fitness` é comprovadamente ótimo.
+    #[serde(default)]
+    pub optimal: bool,
+    /// Curva de convergência da execução, quando o runner a registra.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<TracePoint>,
+}
+
+impl TaskResult {
+    /// Nome da configuração que produziu o resultado, ou o do algoritmo se o worker não o informou.
+    #[must_use]
+    pub fn configuration(&self) -> &str {
+        if self.configuration.is_empty() {
+            &self.algorithm
+        } else {
+            &self.configuration
+        }
+    }
 }
@@ -0,0 +1,17 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::instance::InstanceStats;
+
+/// Resposta a `Request::Status`: andamento das tasks e catálogo de instâncias do host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostStatus {
+    pub total_tasks: usize,
+    pub pending: usize,
+    pub assigned: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub registered_workers: usize,
+    pub instances: BTreeMap<String, InstanceStats>,
+}
@@ -1,21 +1,91 @@
+use std::error::Error;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{
+    config::TaskConfig, generator::SyntheticGraph, graph_format::GraphFormat, island::IslandTask,
+    problem::Problem,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: Uuid,
     pub graph_id: String,
     pub run_number: u32,
-    pub ag_config: String,
+    pub algorithm: String,
+    /// Nome da configuração no relatório, que distingue execuções do mesmo algoritmo
+    /// com parâmetros diferentes; vazio equivale ao nome do algoritmo.
+    #[serde(default)]
+    pub configuration: String,
+    #[serde(default)]
+    pub problem: Problem,
+    #[serde(default)]
+    pub seed: u64,
+    pub ag_config: serde_json::Value,
+    /// Presente quando o grafo é sintético e deve ser gerado pelo worker.
+    #[serde(default)]
+    pub synthetic: Option<SyntheticGraph>,
+    /// Formato do arquivo do grafo definido no experimento; ausente, o worker o detecta.
+    #[serde(default)]
+    pub graph_format: Option<GraphFormat>,
+    /// Presente quando a task é uma ilha de um grupo cooperativo.
+    #[serde(default)]
+    pub island: Option<IslandTask>,
 }
 
 impl Task {
-    pub fn new(graph_id: String, run_number: u32, ag_config: String) -> Self {
-        Self {
+    pub fn new<C: TaskConfig>(
+        graph_id: String,
+        run_number: u32,
+        algorithm: &str,
+        problem: Problem,
+        ag_config: &C,
+    ) -> Result<Self, Box<dyn Error>> {
+        ag_config
+            .validate()
+            .map_err(|e| format!("Configuração inválida para o grafo {graph_id}: {e}"))?;
+
+        Ok(Self {
             id: Uuid::new_v4(),
             graph_id,
             run_number,
-            ag_config,
+            algorithm: algorithm.to_string(),
+            configuration: algorithm.to_string(),
+            problem,
+            seed: rand::random(),
+            ag_config: serde_json::to_value(ag_config)?,
+            synthetic: None,
+            graph_format: None,
+            island: None,
+        })
+    }
+
+    /// Decodifica e valida a configuração da task no tipo esperado pelo runner;
+    /// uma configuração `null` vira `C::omitted()`, se houver.
+    pub fn config<C: TaskConfig>(&self) -> Result<C, String> {
+        let config: C = match C::omitted() {
+            Some(config) if self.ag_config.is_null() => config,
+            _ => serde_json::from_value(self.ag_config.clone()).map_err(|e| {
+                format!(
+                    "Falha ao deserializar a configuração da task {}: {e}",
+                    self.id
+                )
+            })?,
+        };
+        config
+            .validate()
+            .map_err(|e| format!("Configuração inválida na task {}: {e}", self.id))?;
+        Ok(config)
+    }
+
+    /// Nome da configuração da task, ou o do algoritmo se ela não tiver um.
+    #[must_use]
+    pub fn configuration(&self) -> &str {
+        if self.configuration.is_empty() {
+            &self.algorithm
+        } else {
+            &self.configuration
         }
     }
 }
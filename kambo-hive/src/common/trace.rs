@@ -0,0 +1,83 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Ponto de uma curva de convergência: a melhor fitness conhecida após `iteration`
+/// iterações e `elapsed_ms` milissegundos de execução.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TracePoint {
+    pub elapsed_ms: f64,
+    pub iteration: u32,
+    pub best_fitness: f64,
+}
+
+/// Registra a curva de convergência de uma execução, guardando apenas os pontos em
+/// que a melhor fitness muda e reduzindo-os a no máximo `max_points`.
+#[derive(Debug, Clone)]
+pub struct TraceRecorder {
+    start: Instant,
+    max_points: usize,
+    points: Vec<TracePoint>,
+}
+
+impl TraceRecorder {
+    /// `max_points` é pelo menos 2, para manter o primeiro e o último ponto.
+    #[must_use]
+    pub fn new(max_points: usize) -> Self {
+        Self {
+            start: Instant::now(),
+            max_points: max_points.max(2),
+            points: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, iteration: u32, best_fitness: f64) {
+        self.points.push(TracePoint {
+            elapsed_ms: self.start.elapsed().as_secs_f64() * 1000.0,
+            iteration,
+            best_fitness,
+        });
+    }
+
+    /// Registra a melhor fitness após `iteration`; pontos sem mudança são descartados.
+    pub fn record(&mut self, iteration: u32, best_fitness: f64) {
+        if self
+            .points
+            .last()
+            .is_some_and(|point| point.best_fitness == best_fitness)
+        {
+            return;
+        }
+        self.push(iteration, best_fitness);
+        // Mantém a memória limitada em execuções longas, descartando um ponto a cada dois.
+        if self.points.len() > 2 * self.max_points {
+            let last = self.points.len() - 1;
+            let mut index = 0;
+            self.points.retain(|_| {
+                let keep = index % 2 == 0 || index == last;
+                index += 1;
+                keep
+            });
+        }
+    }
+
+    /// Encerra a curva no estado final da execução e a reduz a no máximo
+    /// `max_points` pontos igualmente espaçados, mantendo o primeiro e o último.
+    #[must_use]
+    pub fn finish(mut self, iteration: u32, best_fitness: f64) -> Vec<TracePoint> {
+        self.push(iteration, best_fitness);
+        let count = self.points.len();
+        if count <= self.max_points {
+            return self.points;
+        }
+        let steps = self.max_points - 1;
+        let mut selected: Vec<usize> = (0..=steps)
+            .map(|step| (step * (count - 1) + steps / 2) / steps)
+            .collect();
+        selected.dedup();
+        selected
+            .into_iter()
+            .map(|index| self.points[index])
+            .collect()
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::periodic_saver::write_atomic;
+use crate::common::{Objective, Problem, TaskResult};
+
+/// Melhor solução conhecida de uma instância para uma variante do problema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BestKnown {
+    pub graph_id: String,
+    #[serde(default)]
+    pub problem: Problem,
+    pub fitness: f64,
+    /// Indica que `fitness` é comprovadamente ótimo.
+    #[serde(default)]
+    pub optimal: bool,
+    /// Configuração que encontrou o valor, quando ele veio do cluster.
+    #[serde(default)]
+    pub configuration: Option<String>,
+    #[serde(default)]
+    pub task_id: Option<Uuid>,
+    /// Solução que atingiu `fitness`, quando conhecida.
+    #[serde(default)]
+    pub solution_data: Option<Vec<u8>>,
+}
+
+/// Melhora de um valor conhecido durante a execução do host.
+#[derive(Debug, Clone, Serialize)]
+pub struct NewRecord {
+    pub graph_id: String,
+    pub problem: Problem,
+    pub fitness: f64,
+    pub previous_fitness: f64,
+    pub configuration: String,
+    pub task_id: Uuid,
+}
+
+/// Tabela de melhores valores conhecidos, lida de um arquivo JSON com uma lista de
+/// `BestKnown` e regravada a cada recorde, com a solução que o estabeleceu. Uma
+/// tabela aberta com `load_read_only` só serve de referência e nunca muda.
+#[derive(Debug, Clone, Default)]
+pub struct BestKnownRegistry {
+    path: Option<PathBuf>,
+    read_only: bool,
+    entries: BTreeMap<(String, Problem), BestKnown>,
+    records: Vec<NewRecord>,
+}
+
+impl BestKnownRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lê a tabela de `path`; se o arquivo não existir, começa vazia e o cria no primeiro recorde.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Box<dyn Error>> {
+        let path = path.into();
+        if !path.exists() {
+            info!(
+                "Tabela de melhores valores conhecidos '{}' não existe e será criada",
+                path.display()
+            );
+            return Ok(Self {
+                path: Some(path),
+                ..Self::default()
+            });
+        }
+        let mut registry = Self::read(&path)?;
+        registry.path = Some(path);
+        Ok(registry)
+    }
+
+    /// Lê a tabela de `path` só para consulta: `offer` não a altera nem regrava o
+    /// arquivo. Se o arquivo não existir, a tabela fica vazia.
+    pub fn load_read_only(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let mut registry = if path.exists() {
+            Self::read(path)?
+        } else {
+            warn!(
+                "Tabela de melhores valores conhecidos '{}' não existe",
+                path.display()
+            );
+            Self::default()
+        };
+        registry.read_only = true;
+        Ok(registry)
+    }
+
+    fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut registry = Self::default();
+        let content = fs::read_to_string(path)?;
+        let entries: Vec<BestKnown> = serde_json::from_str(&content).map_err(|e| {
+            format!(
+                "Tabela de melhores valores conhecidos '{}' inválida: {e}",
+                path.display()
+            )
+        })?;
+        for entry in entries {
+            registry
+                .entries
+                .insert((entry.graph_id.clone(), entry.problem), entry);
+        }
+        info!(
+            "{} melhores valores conhecidos lidos de '{}'",
+            registry.entries.len(),
+            path.display()
+        );
+        Ok(registry)
+    }
+
+    #[must_use]
+    pub fn get(&self, graph_id: &str, problem: Problem) -> Option<&BestKnown> {
+        self.entries.get(&(graph_id.to_string(), problem))
+    }
+
+    /// Valores conhecidos melhorados desde que a tabela foi carregada.
+    #[must_use]
+    pub fn records(&self) -> &[NewRecord] {
+        &self.records
+    }
+
+    /// Atualiza a tabela se `result` for melhor que o valor conhecido, ou se a
+    /// instância ainda não tiver valor, e nesse caso regrava o arquivo. Um resultado
+    /// ótimo que empata com o valor conhecido o marca como `optimal` e completa a
+    /// solução, se ela faltar. Devolve verdadeiro se a tabela mudou. Valores marcados
+    /// como `optimal` e tabelas somente leitura nunca mudam.
+    pub fn offer(&mut self, result: &TaskResult, objective: Objective) -> bool {
+        if self.read_only {
+            return false;
+        }
+        let key = (result.graph_id.clone(), result.problem);
+        if let Some(known) = self.entries.get_mut(&key)
+            && !objective.is_better(result.fitness, known.fitness)
+        {
+            if !result.optimal || known.optimal || result.fitness != known.fitness {
+                return false;
+            }
+            info!(
+                "Valor conhecido para '{}' ({}) comprovado ótimo: {}, pela configuração '{}'",
+                result.graph_id,
+                result.problem,
+                result.fitness,
+                result.configuration()
+            );
+            known.optimal = true;
+            if known.solution_data.is_none() && !result.solution_data.is_empty() {
+                known.solution_data = Some(result.solution_data.clone());
+                known.configuration = Some(result.configuration().to_string());
+                known.task_id = Some(result.task_id);
+            }
+            if let Err(e) = self.save() {
+                warn!("Falha ao salvar a tabela de melhores valores conhecidos: {e}");
+            }
+            return true;
+        }
+        let previous = self.entries.get(&key);
+        // Um ótimo declarado não é substituído: um resultado melhor que ele indica
+        // uma solução inválida ou uma tabela errada, e os dois pedem conferência.
+        if let Some(known) = previous.filter(|known| known.optimal) {
+            warn!(
+                "Resultado da task {} para '{}' ({}) tem fitness {}, melhor que o ótimo declarado {}; a tabela não foi alterada",
+                result.task_id, result.graph_id, result.problem, result.fitness, known.fitness
+            );
+            return false;
+        }
+        let previous_fitness = previous.map(|known| known.fitness);
+        match previous {
+            Some(known) => info!(
+                "Novo recorde para '{}' ({}): {} (anterior: {}), pela configuração '{}'",
+                result.graph_id,
+                result.problem,
+                result.fitness,
+                known.fitness,
+                result.configuration()
+            ),
+            None => info!(
+                "Primeiro valor conhecido para '{}' ({}): {}",
+                result.graph_id, result.problem, result.fitness
+            ),
+        }
+
+        self.entries.insert(
+            key,
+            BestKnown {
+                graph_id: result.graph_id.clone(),
+                problem: result.problem,
+                fitness: result.fitness,
+                optimal: result.optimal,
+                configuration: Some(result.configuration().to_string()),
+                task_id: Some(result.task_id),
+                solution_data: Some(result.solution_data.clone()),
+            },
+        );
+        if let Err(e) = self.save() {
+            warn!("Falha ao salvar a tabela de melhores valores conhecidos: {e}");
+        }
+        if let Some(previous_fitness) = previous_fitness {
+            self.records.push(NewRecord {
+                graph_id: result.graph_id.clone(),
+                problem: result.problem,
+                fitness: result.fitness,
+                previous_fitness,
+                configuration: result.configuration().to_string(),
+                task_id: result.task_id,
+            });
+        }
+        true
+    }
+
+    /// Regrava a tabela com `write_atomic`, para que uma falha no meio da escrita
+    /// não destrua os recordes anteriores.
+    fn save(&self) -> Result<(), Box<dyn Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let entries: Vec<&BestKnown> = self.entries.values().collect();
+        write_atomic(path, &serde_json::to_string_pretty(&entries)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(fitness: f64, optimal: bool) -> TaskResult {
+        TaskResult {
+            task_id: Uuid::new_v4(),
+            graph_id: "g.txt".to_string(),
+            algorithm: "exact".to_string(),
+            configuration: String::new(),
+            problem: Problem::Roman,
+            seed: 1,
+            worker_id: Uuid::new_v4(),
+            fitness,
+            solution_data: vec![2, 0, 0],
+            interations_run: 1,
+            processing_time_ms: 1,
+            lower_bound: Some(fitness),
+            optimal,
+            trace: Vec::new(),
+        }
+    }
+
+    /// Tabela com o valor 2 para `g.txt`, sem solução, como se lida de um arquivo.
+    fn registry() -> BestKnownRegistry {
+        let mut registry = BestKnownRegistry::new();
+        registry.entries.insert(
+            ("g.txt".to_string(), Problem::Roman),
+            BestKnown {
+                graph_id: "g.txt".to_string(),
+                problem: Problem::Roman,
+                fitness: 2.0,
+                optimal: false,
+                configuration: None,
+                task_id: None,
+                solution_data: None,
+            },
+        );
+        registry
+    }
+
+    #[test]
+    fn optimal_tie_marks_the_known_value_optimal() {
+        let mut registry = registry();
+        let optimal = result(2.0, true);
+        assert!(registry.offer(&optimal, Objective::Minimize));
+
+        let known = registry.get("g.txt", Problem::Roman).unwrap();
+        assert!(known.optimal);
+        assert_eq!(known.solution_data.as_deref(), Some(&[2, 0, 0][..]));
+        assert_eq!(known.task_id, Some(optimal.task_id));
+        assert_eq!(known.configuration.as_deref(), Some("exact"));
+        assert!(registry.records().is_empty());
+
+        // O valor já é ótimo: outro empate não muda nada.
+        assert!(!registry.offer(&result(2.0, true), Objective::Minimize));
+        let known = registry.get("g.txt", Problem::Roman).unwrap();
+        assert_eq!(known.task_id, Some(optimal.task_id));
+    }
+
+    #[test]
+    fn optimal_tie_keeps_an_existing_solution() {
+        let mut registry = registry();
+        let first = result(2.0, false);
+        registry
+            .entries
+            .get_mut(&("g.txt".to_string(), Problem::Roman))
+            .unwrap()
+            .solution_data = Some(vec![0, 2, 0]);
+        assert!(!registry.offer(&first, Objective::Minimize));
+
+        assert!(registry.offer(&result(2.0, true), Objective::Minimize));
+        let known = registry.get("g.txt", Problem::Roman).unwrap();
+        assert!(known.optimal);
+        assert_eq!(known.solution_data.as_deref(), Some(&[0, 2, 0][..]));
+        assert_eq!(known.task_id, None);
+    }
+
+    #[test]
+    fn worse_optimal_result_is_ignored() {
+        let mut registry = registry();
+        assert!(!registry.offer(&result(3.0, true), Objective::Minimize));
+        assert!(!registry.get("g.txt", Problem::Roman).unwrap().optimal);
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use log::info;
+
+use crate::common::InstanceStats;
+
+/// Instâncias conhecidas pelo host, indexadas pelo nome do arquivo do grafo.
+#[derive(Debug, Clone, Default)]
+pub struct InstanceCatalog {
+    instances: BTreeMap<String, InstanceStats>,
+}
+
+impl InstanceCatalog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, graph_id: &str, stats: InstanceStats) {
+        info!(
+            "Instância '{graph_id}': {} vértices, {} arestas, grau máximo {}, densidade {:.4}, {} componentes",
+            stats.vertices, stats.edges, stats.max_degree, stats.density, stats.components
+        );
+        self.instances.insert(graph_id.to_string(), stats);
+    }
+
+    #[must_use]
+    pub fn get(&self, graph_id: &str) -> Option<&InstanceStats> {
+        self.instances.get(graph_id)
+    }
+
+    /// Tamanho da instância para o escalonamento; instâncias desconhecidas valem 0.
+    #[must_use]
+    pub fn size_of(&self, graph_id: &str) -> usize {
+        self.get(graph_id).map_or(0, InstanceStats::size)
+    }
+
+    #[must_use]
+    pub const fn instances(&self) -> &BTreeMap<String, InstanceStats> {
+        &self.instances
+    }
+}
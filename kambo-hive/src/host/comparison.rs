@@ -0,0 +1,652 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    f64::consts::SQRT_2,
+};
+
+use serde::Serialize;
+
+use super::statistics::GraphStatistics;
+use crate::common::{Objective, Objectives, Problem};
+
+/// Nível de significância dos testes.
+pub const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Valores críticos de Nemenyi para α = 0,05 (amplitude studentizada dividida por √2),
+/// de 2 a 10 configurações.
+const NEMENYI_Q_005: [f64; 9] = [
+    1.960, 2.343, 2.569, 2.728, 2.850, 2.949, 3.031, 3.102, 3.164,
+];
+
+/// Maior amostra sem empates para a qual o p-valor de Wilcoxon é exato.
+const WILCOXON_EXACT_LIMIT: usize = 25;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Wilcoxon {
+    /// Grafos com diferença não nula entre as duas configurações.
+    pub n: usize,
+    /// Soma dos postos em que a primeira configuração tem fitness maior.
+    pub w_plus: f64,
+    pub w_minus: f64,
+    pub p_value: f64,
+    /// Verdadeiro quando o p-valor vem da distribuição exata, e não da aproximação normal.
+    pub exact: bool,
+}
+
+/// Teste U de Mann-Whitney entre as execuções de duas configurações em um grafo.
+#[derive(Debug, Clone, Serialize)]
+pub struct MannWhitney {
+    /// Estatística U da primeira amostra.
+    pub u: f64,
+    /// P-valor bilateral pela aproximação normal, com correção para empates e de continuidade.
+    pub p_value: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Friedman {
+    /// Estatística com correção para empates.
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    pub p_value: f64,
+    pub significant: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AverageRank {
+    pub configuration: String,
+    /// Posto médio nos grafos; 1 é o melhor.
+    pub average_rank: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairwiseComparison {
+    pub first: String,
+    pub second: String,
+    pub wilcoxon: Option<Wilcoxon>,
+    /// Configuração com melhor soma de postos no teste de Wilcoxon, quando ele é significativo.
+    pub wilcoxon_winner: Option<String>,
+    /// Diferença absoluta entre os postos médios de Friedman.
+    pub rank_difference: f64,
+    /// P-valor do teste z sobre os postos médios, ajustado por Holm para todos os pares.
+    pub holm_p_value: Option<f64>,
+    /// Diferença de postos maior que a diferença crítica de Nemenyi.
+    pub nemenyi_significant: Option<bool>,
+}
+
+/// Comparação das configurações de uma variante do problema. Cada grafo em que todas
+/// as configurações têm resultados é um bloco, representado pela fitness média das
+/// execuções de cada configuração.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProblemComparison {
+    pub problem: Problem,
+    pub objective: Objective,
+    pub significance_level: f64,
+    pub graphs: Vec<String>,
+    /// Vazio quando nenhum grafo tem resultados de todas as configurações.
+    pub average_ranks: Vec<AverageRank>,
+    pub friedman: Option<Friedman>,
+    /// Diferença crítica de Nemenyi; disponível para até 10 configurações.
+    pub nemenyi_critical_difference: Option<f64>,
+    pub pairwise: Vec<PairwiseComparison>,
+}
+
+/// Compara as configurações de cada variante com pelo menos duas configurações.
+#[must_use]
+pub fn compare(
+    statistics: &HashMap<String, GraphStatistics>,
+    objectives: &Objectives,
+) -> Vec<ProblemComparison> {
+    let mut configurations: BTreeMap<Problem, BTreeSet<&str>> = BTreeMap::new();
+    for graph in statistics.values() {
+        for (configuration, problem) in graph.configurations.keys() {
+            configurations
+                .entry(*problem)
+                .or_default()
+                .insert(configuration);
+        }
+    }
+
+    configurations
+        .into_iter()
+        .filter(|(_, names)| names.len() >= 2)
+        .map(|(problem, names)| {
+            let names: Vec<&str> = names.into_iter().collect();
+            compare_problem(statistics, problem, objectives.get(problem), &names)
+        })
+        .collect()
+}
+
+/// Compara as configurações `names` de uma variante, nessa ordem.
+#[must_use]
+pub fn compare_problem(
+    statistics: &HashMap<String, GraphStatistics>,
+    problem: Problem,
+    objective: Objective,
+    names: &[&str],
+) -> ProblemComparison {
+    let mut graph_ids: Vec<&String> = statistics.keys().collect();
+    graph_ids.sort();
+    let (graphs, blocks): (Vec<String>, Vec<Vec<f64>>) = graph_ids
+        .into_iter()
+        .filter_map(|graph_id| {
+            let graph = &statistics[graph_id];
+            let means = names
+                .iter()
+                .map(|&name| {
+                    graph
+                        .configurations
+                        .get(&(name.to_string(), problem))
+                        .and_then(|c| c.runs.fitness.summary())
+                        .map(|summary| summary.mean)
+                })
+                .collect::<Option<Vec<f64>>>()?;
+            Some((graph_id.clone(), means))
+        })
+        .unzip();
+
+    let k = names.len();
+    let n = blocks.len();
+    let ranks = if n == 0 {
+        Vec::new()
+    } else {
+        average_ranks(&blocks, objective)
+    };
+    let rank_error = (n > 0)
+        .then(|| (k * (k + 1)) as f64 / (6 * n) as f64)
+        .map(f64::sqrt);
+    let friedman = (n >= 2).then(|| friedman(&blocks, &ranks, objective));
+    let nemenyi_critical_difference = rank_error
+        .zip(NEMENYI_Q_005.get(k.wrapping_sub(2)))
+        .map(|(error, q)| q * error);
+
+    let pairs: Vec<(usize, usize)> = (0..k)
+        .flat_map(|i| (i + 1..k).map(move |j| (i, j)))
+        .collect();
+    let holm = rank_error.map(|error| {
+        let p_values: Vec<f64> = pairs
+            .iter()
+            .map(|&(i, j)| two_sided_normal_p((ranks[i] - ranks[j]).abs() / error))
+            .collect();
+        holm_adjust(&p_values)
+    });
+
+    let pairwise = pairs
+        .iter()
+        .enumerate()
+        .map(|(index, &(i, j))| {
+            let first: Vec<f64> = blocks.iter().map(|block| block[i]).collect();
+            let second: Vec<f64> = blocks.iter().map(|block| block[j]).collect();
+            let wilcoxon = wilcoxon(&first, &second);
+            let wilcoxon_winner = wilcoxon
+                .as_ref()
+                .filter(|test| test.p_value < SIGNIFICANCE_LEVEL)
+                .map(|test| {
+                    // W+ soma os grafos em que a primeira configuração tem fitness maior.
+                    let first_higher = test.w_plus > test.w_minus;
+                    let first_better = first_higher == (objective == Objective::Maximize);
+                    if first_better { names[i] } else { names[j] }.to_string()
+                });
+            let rank_difference = ranks
+                .get(i)
+                .zip(ranks.get(j))
+                .map_or(0.0, |(a, b)| (a - b).abs());
+            PairwiseComparison {
+                first: names[i].to_string(),
+                second: names[j].to_string(),
+                wilcoxon,
+                wilcoxon_winner,
+                rank_difference,
+                holm_p_value: holm.as_ref().map(|p_values| p_values[index]),
+                nemenyi_significant: nemenyi_critical_difference.map(|cd| rank_difference > cd),
+            }
+        })
+        .collect();
+
+    ProblemComparison {
+        problem,
+        objective,
+        significance_level: SIGNIFICANCE_LEVEL,
+        graphs,
+        average_ranks: names
+            .iter()
+            .zip(ranks.iter().copied())
+            .map(|(name, average_rank)| AverageRank {
+                configuration: (*name).to_string(),
+                average_rank,
+            })
+            .collect(),
+        friedman,
+        nemenyi_critical_difference,
+        pairwise,
+    }
+}
+
+/// Postos de `values` do melhor (1) para o pior, com a média dos postos em empates.
+fn rank(values: &[f64], order: impl Fn(f64, f64) -> Ordering) -> Vec<f64> {
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    indices.sort_by(|&a, &b| order(values[a], values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < indices.len() {
+        let mut end = start + 1;
+        while end < indices.len() && values[indices[end]] == values[indices[start]] {
+            end += 1;
+        }
+        let average = (start + end + 1) as f64 / 2.0;
+        for &index in &indices[start..end] {
+            ranks[index] = average;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Tamanhos dos grupos de valores empatados.
+fn tie_sizes(values: &[f64]) -> Vec<usize> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+        .chunk_by(|a, b| a == b)
+        .map(<[f64]>::len)
+        .filter(|&size| size > 1)
+        .collect()
+}
+
+/// Posto médio de cada configuração nos blocos.
+fn average_ranks(blocks: &[Vec<f64>], objective: Objective) -> Vec<f64> {
+    let mut sums = vec![0.0; blocks[0].len()];
+    for block in blocks {
+        for (sum, rank) in sums
+            .iter_mut()
+            .zip(rank(block, |a, b| objective.compare(a, b)))
+        {
+            *sum += rank;
+        }
+    }
+    sums.iter().map(|sum| sum / blocks.len() as f64).collect()
+}
+
+fn friedman(blocks: &[Vec<f64>], ranks: &[f64], objective: Objective) -> Friedman {
+    let n = blocks.len() as f64;
+    let k = ranks.len() as f64;
+    let spread: f64 = ranks.iter().map(|r| (r - (k + 1.0) / 2.0).powi(2)).sum();
+    let mut statistic = 12.0 * n / (k * (k + 1.0)) * spread;
+    let ties: usize = blocks
+        .iter()
+        .flat_map(|block| {
+            let ranked = rank(block, |a, b| objective.compare(a, b));
+            tie_sizes(&ranked)
+        })
+        .map(|t| t * t * t - t)
+        .sum();
+    let correction = 1.0 - ties as f64 / (n * (k * k * k - k));
+    if correction > 0.0 {
+        statistic /= correction;
+    }
+    let degrees_of_freedom = ranks.len() - 1;
+    let p_value = chi_square_survival(statistic, degrees_of_freedom as f64);
+    Friedman {
+        statistic,
+        degrees_of_freedom,
+        p_value,
+        significant: p_value < SIGNIFICANCE_LEVEL,
+    }
+}
+
+/// Teste de postos sinalizados de Wilcoxon, bilateral, para amostras pareadas.
+/// `None` se todas as diferenças forem nulas.
+fn wilcoxon(first: &[f64], second: &[f64]) -> Option<Wilcoxon> {
+    let differences: Vec<f64> = first
+        .iter()
+        .zip(second)
+        .map(|(a, b)| a - b)
+        .filter(|&d| d != 0.0)
+        .collect();
+    let n = differences.len();
+    if n == 0 {
+        return None;
+    }
+    let magnitudes: Vec<f64> = differences.iter().map(|d| d.abs()).collect();
+    let ranks = rank(&magnitudes, |a, b| a.total_cmp(&b));
+    let w_plus = differences
+        .iter()
+        .zip(&ranks)
+        .filter(|(d, _)| **d > 0.0)
+        .fold(0.0, |sum, (_, r)| sum + r);
+    let total = (n * (n + 1)) as f64 / 2.0;
+    let w_minus = total - w_plus;
+    let ties = tie_sizes(&magnitudes);
+
+    let exact = ties.is_empty() && n <= WILCOXON_EXACT_LIMIT;
+    let p_value = if exact {
+        wilcoxon_exact_p(n, w_plus.min(w_minus) as usize)
+    } else {
+        let mean = total / 2.0;
+        let correction: usize = ties.iter().map(|&t| t * t * t - t).sum();
+        let variance = (n * (n + 1) * (2 * n + 1)) as f64 / 24.0 - correction as f64 / 48.0;
+        if variance <= 0.0 {
+            1.0
+        } else {
+            let z = ((w_plus - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+            two_sided_normal_p(z)
+        }
+    };
+    Some(Wilcoxon {
+        n,
+        w_plus,
+        w_minus,
+        p_value,
+        exact,
+    })
+}
+
+/// Teste U de Mann-Whitney bilateral para amostras independentes. `None` se alguma
+/// das amostras estiver vazia.
+#[must_use]
+pub fn mann_whitney(first: &[f64], second: &[f64]) -> Option<MannWhitney> {
+    if first.is_empty() || second.is_empty() {
+        return None;
+    }
+    let n1 = first.len() as f64;
+    let n2 = second.len() as f64;
+    let combined: Vec<f64> = first.iter().chain(second).copied().collect();
+    let ranks = rank(&combined, |a, b| a.total_cmp(&b));
+    let rank_sum: f64 = ranks[..first.len()].iter().sum();
+    let u = rank_sum - n1 * (n1 + 1.0) / 2.0;
+
+    let n = n1 + n2;
+    let ties: usize = tie_sizes(&combined).iter().map(|&t| t * t * t - t).sum();
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties as f64 / (n * (n - 1.0)));
+    let p_value = if variance <= 0.0 {
+        1.0
+    } else {
+        let z = ((u - n1 * n2 / 2.0).abs() - 0.5).max(0.0) / variance.sqrt();
+        two_sided_normal_p(z)
+    };
+    Some(MannWhitney { u, p_value })
+}
+
+/// P-valor bilateral exato de Wilcoxon: `2 P(W <= w)` sob a hipótese nula, em que
+/// cada uma das `2^n` atribuições de sinais é igualmente provável.
+fn wilcoxon_exact_p(n: usize, w: usize) -> f64 {
+    let max = n * (n + 1) / 2;
+    let mut counts = vec![0.0_f64; max + 1];
+    counts[0] = 1.0;
+    for rank in 1..=n {
+        for sum in (rank..=max).rev() {
+            counts[sum] += counts[sum - rank];
+        }
+    }
+    let at_most: f64 = counts[..=w].iter().sum();
+    (2.0 * at_most / 2f64.powi(n as i32)).min(1.0)
+}
+
+/// Ajuste de Holm: o i-ésimo menor p-valor é multiplicado por `m - i`, mantendo a
+/// sequência monótona.
+fn holm_adjust(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|&a, &b| p_values[a].total_cmp(&p_values[b]));
+    let mut adjusted = vec![0.0; m];
+    let mut running = 0.0_f64;
+    for (position, &index) in order.iter().enumerate() {
+        running = running.max(((m - position) as f64 * p_values[index]).min(1.0));
+        adjusted[index] = running;
+    }
+    adjusted
+}
+
+fn two_sided_normal_p(z: f64) -> f64 {
+    erfc(z.abs() / SQRT_2).min(1.0)
+}
+
+/// Função erro complementar (Numerical Recipes, erro relativo abaixo de 1,2e-7).
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let polynomial = t.mul_add(0.170_872_77, -0.822_152_23);
+    let polynomial = t.mul_add(polynomial, 1.488_515_87);
+    let polynomial = t.mul_add(polynomial, -1.135_203_98);
+    let polynomial = t.mul_add(polynomial, 0.278_868_07);
+    let polynomial = t.mul_add(polynomial, -0.186_288_06);
+    let polynomial = t.mul_add(polynomial, 0.096_784_18);
+    let polynomial = t.mul_add(polynomial, 0.374_091_96);
+    let polynomial = t.mul_add(polynomial, 1.000_023_68);
+    let polynomial = t.mul_add(polynomial, -1.265_512_23);
+    let value = t * (-x * x + polynomial).exp();
+    if x >= 0.0 { value } else { 2.0 - value }
+}
+
+/// `P(X > x)` para uma qui-quadrado com `degrees_of_freedom` graus de liberdade.
+fn chi_square_survival(x: f64, degrees_of_freedom: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    upper_regularized_gamma(degrees_of_freedom / 2.0, x / 2.0)
+}
+
+/// Função gama incompleta superior regularizada Q(a, x), por série quando
+/// `x < a + 1` e por fração contínua (Lentz) caso contrário.
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-14;
+    const MAX_ITERATIONS: usize = 500;
+    let log_prefix = a.mul_add(x.ln(), -x) - ln_gamma(a);
+
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        return (1.0 - sum * log_prefix.exp()).clamp(0.0, 1.0);
+    }
+
+    let tiny = f64::MIN_POSITIVE / EPSILON;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut fraction = d;
+    for i in 1..=MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an.mul_add(d, b);
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        fraction *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    (fraction * log_prefix.exp()).clamp(0.0, 1.0)
+}
+
+/// Logaritmo da função gama pela aproximação de Lanczos (g = 7).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    0.5f64.mul_add((2.0 * std::f64::consts::PI).ln(), (x + 0.5) * t.ln()) - t + series.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "esperado {expected}, obtido {actual}"
+        );
+    }
+
+    #[test]
+    fn friedman_and_nemenyi_match_demsar_example() {
+        // Demšar (2006), seção 3.2.2: quatro variantes do C4.5 em 14 conjuntos de dados,
+        // postos médios 3,143, 2,000, 2,893 e 1,964, χ²F = 9,28 e CD = 1,25. O artigo
+        // não corrige os empates, então os blocos aqui não têm empates.
+        let ranks = [44.0 / 14.0, 28.0 / 14.0, 40.5 / 14.0, 27.5 / 14.0];
+        let blocks = vec![vec![1.0, 2.0, 3.0, 4.0]; 14];
+        let result = friedman(&blocks, &ranks, Objective::Maximize);
+        assert_close(result.statistic, 9.28, 0.005);
+        assert_eq!(result.degrees_of_freedom, 3);
+        // F_F de Iman e Davenport, que o artigo deriva de χ²F.
+        let iman_davenport = 13.0 * result.statistic / (14.0 * 3.0 - result.statistic);
+        assert_close(iman_davenport, 3.69, 0.005);
+        assert_close(result.p_value, 0.025_807_5, 1e-6);
+        assert!(result.significant);
+
+        let critical_difference = NEMENYI_Q_005[4 - 2] * (20.0_f64 / (6.0 * 14.0)).sqrt();
+        assert_close(critical_difference, 1.25, 0.005);
+    }
+
+    #[test]
+    fn average_ranks_split_ties_and_follow_objective() {
+        let blocks = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 3.0, 2.0],
+            vec![1.0, 2.0, 3.0],
+            vec![2.0, 1.0, 3.0],
+        ];
+        assert_eq!(
+            average_ranks(&blocks, Objective::Minimize),
+            [1.25, 2.0, 2.75]
+        );
+        assert_eq!(
+            average_ranks(&blocks, Objective::Maximize),
+            [2.75, 2.0, 1.25]
+        );
+        assert_eq!(
+            rank(&[5.0, 1.0, 5.0, 3.0], |a, b| a.total_cmp(&b)),
+            [3.5, 1.0, 3.5, 2.0]
+        );
+    }
+
+    #[test]
+    fn friedman_without_ties_uses_chi_square_with_k_minus_one_degrees() {
+        let blocks = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 3.0, 2.0],
+            vec![1.0, 2.0, 3.0],
+            vec![2.0, 1.0, 3.0],
+        ];
+        let ranks = average_ranks(&blocks, Objective::Minimize);
+        let result = friedman(&blocks, &ranks, Objective::Minimize);
+        // χ² = 12·4/(3·4)·Σ(R - 2)² = 4,5; com 2 graus de liberdade, p = e^(-χ²/2).
+        assert_close(result.statistic, 4.5, 1e-12);
+        assert_close(result.p_value, (-2.25_f64).exp(), 1e-9);
+        assert!(!result.significant);
+    }
+
+    #[test]
+    fn friedman_corrects_for_ties_like_scipy() {
+        // Mesma correção de scipy.stats.friedmanchisquare: χ² / (1 - Σ(t³ - t) / (n·k·(k² - 1))).
+        let blocks = vec![
+            vec![1.0, 1.0, 2.0],
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 2.0, 3.0],
+        ];
+        let ranks = average_ranks(&blocks, Objective::Minimize);
+        let result = friedman(&blocks, &ranks, Objective::Minimize);
+        let uncorrected = 31.0 / 6.0;
+        assert_close(result.statistic, uncorrected / (1.0 - 6.0 / 72.0), 1e-12);
+        assert_close(result.p_value, 0.059_714_415_7, 1e-8);
+    }
+
+    #[test]
+    fn wilcoxon_exact_p_values_match_tables() {
+        // Cinco diferenças positivas: o menor p-valor possível, 2/2⁵.
+        let test = wilcoxon(&[2.0, 4.0, 6.0, 8.0, 10.0], &[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+        assert!(test.exact);
+        assert_eq!((test.w_plus, test.w_minus), (15.0, 0.0));
+        assert_close(test.p_value, 0.0625, 1e-12);
+
+        // n = 10 e W = 8, o valor crítico bilateral de α = 0,05: p = 2·25/1024.
+        let differences = [-1.0, 2.0, -3.0, -4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        let zeros = [0.0; 10];
+        let test = wilcoxon(&differences, &zeros).unwrap();
+        assert_eq!(test.n, 10);
+        assert_eq!(test.w_minus, 8.0);
+        assert_close(test.p_value, 50.0 / 1024.0, 1e-12);
+        assert_close(wilcoxon_exact_p(10, 8), 0.048_828_125, 1e-12);
+    }
+
+    #[test]
+    fn wilcoxon_uses_normal_approximation_with_ties() {
+        let differences = [1.0, 1.0, 2.0, 2.0, 2.0, -3.0, 4.0, 5.0, 6.0, 7.0];
+        let zeros = [0.0; 10];
+        let test = wilcoxon(&differences, &zeros).unwrap();
+        assert!(!test.exact);
+        assert_eq!((test.w_plus, test.w_minus), (49.0, 6.0));
+        // z = (|49 - 27,5| - 0,5) / √(385/4 - (6 + 24)/48), com correção de continuidade.
+        assert_close(test.p_value, 0.031_753_34, 1e-6);
+
+        assert!(wilcoxon(&[1.0, 2.0], &[1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn mann_whitney_matches_scipy_asymptotic() {
+        // scipy.stats.mannwhitneyu([1, 2, 3], [4, 5, 6], method="asymptotic").
+        let test = mann_whitney(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(test.u, 0.0);
+        assert_close(test.p_value, 0.080_855_6, 1e-6);
+
+        // Com empates entre as amostras, a variância é corrigida.
+        let test = mann_whitney(&[1.0, 2.0, 2.0, 3.0], &[2.0, 3.0, 4.0, 5.0, 5.0]).unwrap();
+        assert_eq!(test.u, 2.5);
+        assert_close(test.p_value, 0.078_545_85, 1e-6);
+
+        assert!(mann_whitney(&[], &[1.0]).is_none());
+    }
+
+    #[test]
+    fn holm_adjustment_is_monotone() {
+        let adjusted = holm_adjust(&[0.01, 0.04, 0.03]);
+        let expected = [0.03, 0.06, 0.06];
+        for (actual, expected) in adjusted.into_iter().zip(expected) {
+            assert_close(actual, expected, 1e-12);
+        }
+        assert_eq!(holm_adjust(&[0.6, 0.9]), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn distributions_match_reference_values() {
+        assert_close(erfc(1.0), 0.157_299_207_050_285, 2e-7);
+        assert_close(erfc(-1.0), 2.0 - 0.157_299_207_050_285, 2e-7);
+        assert_close(two_sided_normal_p(1.959_964), 0.05, 1e-6);
+        assert_close(ln_gamma(5.0), 24.0_f64.ln(), 1e-12);
+        assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln(), 1e-12);
+        // Valores críticos de α = 0,05 das tabelas da qui-quadrado.
+        assert_close(chi_square_survival(3.841, 1.0), 0.05, 1e-4);
+        assert_close(chi_square_survival(7.815, 3.0), 0.05, 1e-4);
+        assert_close(chi_square_survival(18.307, 10.0), 0.05, 1e-4);
+        assert_close(chi_square_survival(0.0, 2.0), 1.0, 0.0);
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use super::{export::csv_field, statistics::GraphStatistics};
+use crate::common::{Problem, TracePoint};
+
+/// Pontos das curvas médias incluídas no relatório.
+pub const DEFAULT_CURVE_POINTS: usize = 100;
+
+/// Melhor fitness média das execuções em um instante.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CurvePoint {
+    pub elapsed_ms: f64,
+    pub mean_best_fitness: f64,
+    pub min_best_fitness: f64,
+    pub max_best_fitness: f64,
+    /// Execuções que já tinham uma solução nesse instante.
+    pub runs: usize,
+}
+
+/// Curva de convergência média de uma configuração em uma instância.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConvergenceCurve {
+    pub graph_id: String,
+    pub configuration: String,
+    pub problem: Problem,
+    /// Execuções com curva registrada.
+    pub runs: usize,
+    pub points: Vec<CurvePoint>,
+}
+
+/// Melhor fitness de `trace` em `elapsed_ms`, ou `None` se a execução ainda não
+/// tinha solução. Depois do fim da execução vale o resultado final.
+fn best_at(trace: &[TracePoint], elapsed_ms: f64) -> Option<f64> {
+    let index = trace.partition_point(|point| point.elapsed_ms <= elapsed_ms);
+    index.checked_sub(1).map(|index| trace[index].best_fitness)
+}
+
+/// Média das curvas `traces` em `points` instantes igualmente espaçados entre o
+/// início e o fim da execução mais longa.
+#[must_use]
+pub fn average(traces: &[Vec<TracePoint>], points: usize) -> Vec<CurvePoint> {
+    let end = traces
+        .iter()
+        .filter_map(|trace| trace.last())
+        .map(|point| point.elapsed_ms)
+        .fold(0.0, f64::max);
+    let points = points.max(2);
+    (0..points)
+        .filter_map(|step| {
+            let elapsed_ms = end * step as f64 / (points - 1) as f64;
+            let values: Vec<f64> = traces
+                .iter()
+                .filter_map(|trace| best_at(trace, elapsed_ms))
+                .collect();
+            if values.is_empty() {
+                return None;
+            }
+            Some(CurvePoint {
+                elapsed_ms,
+                mean_best_fitness: values.iter().sum::<f64>() / values.len() as f64,
+                min_best_fitness: values.iter().copied().fold(f64::INFINITY, f64::min),
+                max_best_fitness: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                runs: values.len(),
+            })
+        })
+        .collect()
+}
+
+/// Curvas médias de cada configuração em cada instância, ordenadas por instância,
+/// variante e configuração.
+#[must_use]
+pub fn curves(
+    statistics: &HashMap<String, GraphStatistics>,
+    points: usize,
+) -> Vec<ConvergenceCurve> {
+    let mut graph_ids: Vec<&String> = statistics.keys().collect();
+    graph_ids.sort();
+    let mut curves = Vec::new();
+    for graph_id in graph_ids {
+        let mut configurations: Vec<_> = statistics[graph_id].configurations.iter().collect();
+        configurations.sort_by(|((a, p), _), ((b, q), _)| p.cmp(q).then_with(|| a.cmp(b)));
+        for ((configuration, problem), stats) in configurations {
+            if stats.traces.is_empty() {
+                continue;
+            }
+            curves.push(ConvergenceCurve {
+                graph_id: graph_id.clone(),
+                configuration: configuration.clone(),
+                problem: *problem,
+                runs: stats.traces.len(),
+                points: average(&stats.traces, points),
+            });
+        }
+    }
+    curves
+}
+
+/// Formato da saída das curvas de convergência.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvergenceFormat {
+    /// Uma linha por ponto de cada curva.
+    Csv,
+    /// Um gráfico por instância, com uma curva por configuração.
+    Svg,
+}
+
+impl ConvergenceFormat {
+    /// SVG para `.svg`; qualquer outra extensão é CSV.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("svg") => Self::Svg,
+            _ => Self::Csv,
+        }
+    }
+}
+
+const fn default_points() -> usize {
+    DEFAULT_CURVE_POINTS
+}
+
+/// Saída das curvas de convergência declarada no experimento.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConvergenceExport {
+    pub path: String,
+    /// Padrão: o formato indicado pela extensão de `path`.
+    #[serde(default)]
+    pub format: Option<ConvergenceFormat>,
+    /// Pontos de cada curva média.
+    #[serde(default = "default_points")]
+    pub points: usize,
+}
+
+impl ConvergenceExport {
+    #[must_use]
+    pub fn format(&self) -> ConvergenceFormat {
+        self.format
+            .unwrap_or_else(|| ConvergenceFormat::from_path(Path::new(&self.path)))
+    }
+}
+
+const CSV_HEADER: &str = "graph_id,configuration,problem,elapsed_ms,mean_best_fitness,min_best_fitness,max_best_fitness,runs";
+
+fn render_csv(curves: &[ConvergenceCurve]) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+    for curve in curves {
+        for point in &curve.points {
+            lines.push(format!(
+                "{},{},{},{},{},{},{},{}",
+                csv_field(&curve.graph_id),
+                csv_field(&curve.configuration),
+                curve.problem,
+                point.elapsed_ms,
+                point.mean_best_fitness,
+                point.min_best_fitness,
+                point.max_best_fitness,
+                point.runs
+            ));
+        }
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+const SVG_WIDTH: f64 = 760.0;
+const PANEL_HEIGHT: f64 = 300.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 180.0;
+const MARGIN_TOP: f64 = 30.0;
+const MARGIN_BOTTOM: f64 = 45.0;
+const TICKS: usize = 5;
+const PALETTE: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn tick_label(value: f64) -> String {
+    if value.abs() >= 100.0 || value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+/// Gráfico de uma instância e variante: eixos, marcas, uma linha por configuração e a legenda.
+fn render_panel(title: &str, curves: &[&ConvergenceCurve], lines: &mut Vec<String>) {
+    let width = SVG_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let height = PANEL_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let points = curves.iter().flat_map(|curve| &curve.points);
+    let x_max = points
+        .clone()
+        .map(|point| point.elapsed_ms)
+        .fold(0.0, f64::max);
+    let x_max = if x_max > 0.0 { x_max } else { 1.0 };
+    let (mut y_min, mut y_max) = points.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+        (lo.min(p.mean_best_fitness), hi.max(p.mean_best_fitness))
+    });
+    if y_min >= y_max {
+        y_min -= 1.0;
+        y_max += 1.0;
+    }
+    let x = |value: f64| MARGIN_LEFT + value / x_max * width;
+    let y = |value: f64| MARGIN_TOP + (y_max - value) / (y_max - y_min) * height;
+
+    lines.push(format!(
+        r#"<text x="{:.1}" y="18" text-anchor="middle" font-weight="bold">{}</text>"#,
+        MARGIN_LEFT + width / 2.0,
+        xml_escape(title)
+    ));
+    lines.push(format!(
+        r##"<rect x="{MARGIN_LEFT}" y="{MARGIN_TOP}" width="{width}" height="{height}" fill="none" stroke="#444"/>"##
+    ));
+    for tick in 0..=TICKS {
+        let fraction = tick as f64 / TICKS as f64;
+        let x_value = x_max * fraction;
+        let y_value = y_min + (y_max - y_min) * fraction;
+        lines.push(format!(
+            r##"<line x1="{0:.1}" y1="{1:.1}" x2="{0:.1}" y2="{2:.1}" stroke="#444"/><text x="{0:.1}" y="{3:.1}" text-anchor="middle">{4}</text>"##,
+            x(x_value),
+            MARGIN_TOP + height,
+            MARGIN_TOP + height + 5.0,
+            MARGIN_TOP + height + 18.0,
+            tick_label(x_value)
+        ));
+        lines.push(format!(
+            r##"<line x1="{0:.1}" y1="{1:.1}" x2="{2:.1}" y2="{1:.1}" stroke="#444"/><text x="{3:.1}" y="{4:.1}" text-anchor="end">{5}</text>"##,
+            MARGIN_LEFT - 5.0,
+            y(y_value),
+            MARGIN_LEFT,
+            MARGIN_LEFT - 8.0,
+            y(y_value) + 4.0,
+            tick_label(y_value)
+        ));
+    }
+    lines.push(format!(
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">Time (ms)</text>"#,
+        MARGIN_LEFT + width / 2.0,
+        PANEL_HEIGHT - 8.0
+    ));
+    lines.push(format!(
+        r#"<text transform="translate(16 {:.1}) rotate(-90)" text-anchor="middle">Mean best fitness</text>"#,
+        MARGIN_TOP + height / 2.0
+    ));
+
+    for (index, curve) in curves.iter().enumerate() {
+        let color = PALETTE[index % PALETTE.len()];
+        let path: Vec<String> = curve
+            .points
+            .iter()
+            .map(|point| {
+                format!(
+                    "{:.1},{:.1}",
+                    x(point.elapsed_ms),
+                    y(point.mean_best_fitness)
+                )
+            })
+            .collect();
+        lines.push(format!(
+            r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="1.5"/>"#,
+            path.join(" ")
+        ));
+        let legend_x = MARGIN_LEFT + width + 15.0;
+        let legend_y = MARGIN_TOP + 10.0 + 18.0 * index as f64;
+        lines.push(format!(
+            r#"<line x1="{legend_x:.1}" y1="{legend_y:.1}" x2="{:.1}" y2="{legend_y:.1}" stroke="{color}" stroke-width="2"/><text x="{:.1}" y="{:.1}">{} ({})</text>"#,
+            legend_x + 20.0,
+            legend_x + 26.0,
+            legend_y + 4.0,
+            xml_escape(&curve.configuration),
+            curve.runs
+        ));
+    }
+}
+
+fn render_svg(curves: &[ConvergenceCurve]) -> String {
+    let mut panels: BTreeMap<(&str, Problem), Vec<&ConvergenceCurve>> = BTreeMap::new();
+    for curve in curves {
+        panels
+            .entry((curve.graph_id.as_str(), curve.problem))
+            .or_default()
+            .push(curve);
+    }
+    let total_height = PANEL_HEIGHT * panels.len().max(1) as f64;
+    let mut lines = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{total_height}" font-family="sans-serif" font-size="12">"#
+    )];
+    lines.push(r#"<rect width="100%" height="100%" fill="white"/>"#.to_string());
+    for (index, ((graph_id, problem), curves)) in panels.into_iter().enumerate() {
+        lines.push(format!(
+            r#"<g transform="translate(0 {:.1})">"#,
+            PANEL_HEIGHT * index as f64
+        ));
+        render_panel(&format!("{graph_id} ({problem})"), &curves, &mut lines);
+        lines.push("</g>".to_string());
+    }
+    lines.push("</svg>".to_string());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Calcula as curvas médias e as grava em `spec.path`.
+pub fn write(
+    spec: &ConvergenceExport,
+    statistics: &HashMap<String, GraphStatistics>,
+) -> Result<(), Box<dyn Error>> {
+    let curves = curves(statistics, spec.points);
+    let content = match spec.format() {
+        ConvergenceFormat::Csv => render_csv(&curves),
+        ConvergenceFormat::Svg => render_svg(&curves),
+    };
+    fs::write(&spec.path, content).map_err(|e| {
+        format!(
+            "Falha ao gravar as curvas de convergência '{}': {e}",
+            spec.path
+        )
+    })?;
+    info!(
+        "{} curvas de convergência salvas em '{}'",
+        curves.len(),
+        spec.path
+    );
+    Ok(())
+}
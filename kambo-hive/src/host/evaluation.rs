@@ -0,0 +1,233 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use log::{debug, warn};
+use uuid::Uuid;
+
+use crate::common::{EvaluationJob, Task};
+
+/// Prazo para um worker devolver um job de avaliação. Vencido o prazo, o job volta
+/// para a fila, para que um avaliador que caiu não prenda o lote até o coordenador
+/// desistir dele.
+pub const EVALUATION_LEASE: Duration = Duration::from_secs(10);
+
+/// Job entregue a um worker e o prazo para ele devolvê-lo.
+#[derive(Debug)]
+struct Lease {
+    job: EvaluationJob,
+    worker_id: Uuid,
+    deadline: Instant,
+}
+
+/// Lote de avaliação de um coordenador e os fitness já recebidos.
+#[derive(Debug)]
+struct Batch {
+    task_id: Uuid,
+    fitnesses: Vec<Option<f64>>,
+    remaining_jobs: usize,
+}
+
+/// Jobs de avaliação do modo mestre-escravo.
+///
+/// Os lotes são divididos em jobs pequenos, um por worker capaz de avaliá-los,
+/// e os jobs são entregues antes de qualquer task para que o coordenador espere o mínimo.
+#[derive(Debug, Default)]
+pub struct EvaluationQueue {
+    pending: VecDeque<EvaluationJob>,
+    assigned: HashMap<Uuid, Lease>, // JobId -> job atribuído
+    batches: HashMap<Uuid, Batch>,
+}
+
+impl EvaluationQueue {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Divide `individuals` da task `task` em até `workers` jobs e devolve o id do lote.
+    pub fn submit(
+        &mut self,
+        task: &Task,
+        evaluator: &str,
+        individuals: Vec<Vec<u8>>,
+        workers: usize,
+    ) -> Uuid {
+        let batch_id = Uuid::new_v4();
+        let chunk_size = individuals.len().div_ceil(workers.max(1)).max(1);
+        let mut remaining_jobs = 0;
+        let mut offset = 0;
+        for chunk in individuals.chunks(chunk_size) {
+            self.pending.push_back(EvaluationJob {
+                id: Uuid::new_v4(),
+                batch_id,
+                offset,
+                evaluator: evaluator.to_string(),
+                graph_id: task.graph_id.clone(),
+                problem: task.problem,
+                synthetic: task.synthetic.clone(),
+                graph_format: task.graph_format,
+                individuals: chunk.to_vec(),
+            });
+            offset += chunk.len();
+            remaining_jobs += 1;
+        }
+        debug!(
+            "Lote {batch_id} da task {} dividido em {remaining_jobs} jobs",
+            task.id
+        );
+        self.batches.insert(
+            batch_id,
+            Batch {
+                task_id: task.id,
+                fitnesses: vec![None; individuals.len()],
+                remaining_jobs,
+            },
+        );
+        batch_id
+    }
+
+    /// Próximo job cujo avaliador `worker_id` executa.
+    pub fn next(
+        &mut self,
+        worker_id: Uuid,
+        supports: impl Fn(&str) -> bool,
+    ) -> Option<EvaluationJob> {
+        let index = self
+            .pending
+            .iter()
+            .position(|job| supports(&job.evaluator))?;
+        let job = self.pending.remove(index)?;
+        self.assigned.insert(
+            job.id,
+            Lease {
+                job: job.clone(),
+                worker_id,
+                deadline: Instant::now() + EVALUATION_LEASE,
+            },
+        );
+        Some(job)
+    }
+
+    /// Devolve `job` ao início da fila, a menos que o seu lote tenha sido descartado.
+    fn requeue(&mut self, job: EvaluationJob) -> bool {
+        if self.batches.contains_key(&job.batch_id) {
+            self.pending.push_front(job);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Devolve à fila os jobs cujo prazo venceu e devolve quantos voltaram.
+    pub fn reclaim_expired(&mut self) -> usize {
+        let now = Instant::now();
+        let expired: Vec<Uuid> = self
+            .assigned
+            .iter()
+            .filter(|(_, lease)| lease.deadline <= now)
+            .map(|(job_id, _)| *job_id)
+            .collect();
+        let mut reclaimed = 0;
+        for job_id in expired {
+            if let Some(lease) = self.assigned.remove(&job_id) {
+                warn!(
+                    "Job de avaliação {job_id} não foi devolvido pelo worker {} em {} segundos; voltando para a fila",
+                    lease.worker_id,
+                    EVALUATION_LEASE.as_secs()
+                );
+                reclaimed += usize::from(self.requeue(lease.job));
+            }
+        }
+        reclaimed
+    }
+
+    /// Devolve à fila os jobs atribuídos a `worker_id`, por exemplo quando ele se
+    /// desconecta, e devolve quantos voltaram.
+    pub fn release_worker(&mut self, worker_id: Uuid) -> usize {
+        let released: Vec<Uuid> = self
+            .assigned
+            .iter()
+            .filter(|(_, lease)| lease.worker_id == worker_id)
+            .map(|(job_id, _)| *job_id)
+            .collect();
+        let mut requeued = 0;
+        for job_id in released {
+            if let Some(lease) = self.assigned.remove(&job_id) {
+                requeued += usize::from(self.requeue(lease.job));
+            }
+        }
+        requeued
+    }
+
+    pub fn complete(&mut self, job_id: Uuid, fitnesses: Vec<f64>) -> Result<(), String> {
+        // Um job cujo prazo venceu ainda pode estar na fila; a resposta atrasada vale.
+        let job = match self.assigned.remove(&job_id) {
+            Some(lease) => lease.job,
+            None => self
+                .pending
+                .iter()
+                .position(|job| job.id == job_id)
+                .and_then(|index| self.pending.remove(index))
+                .ok_or_else(|| format!("Job de avaliação {job_id} não está atribuído"))?,
+        };
+        if fitnesses.len() != job.individuals.len() {
+            let message = format!(
+                "Job {job_id} devolveu {} fitness para {} indivíduos",
+                fitnesses.len(),
+                job.individuals.len()
+            );
+            self.requeue(job);
+            return Err(message);
+        }
+        // O lote pode ter sido descartado se a task do coordenador terminou.
+        if let Some(batch) = self.batches.get_mut(&job.batch_id) {
+            for (slot, fitness) in batch.fitnesses[job.offset..].iter_mut().zip(fitnesses) {
+                *slot = Some(fitness);
+            }
+            batch.remaining_jobs -= 1;
+        }
+        Ok(())
+    }
+
+    pub fn fail(&mut self, job_id: Uuid) {
+        if let Some(lease) = self.assigned.remove(&job_id) {
+            self.requeue(lease.job);
+        } else {
+            warn!("Tentando marcar um job de avaliação não atribuído: {job_id}");
+        }
+    }
+
+    /// `None` se o lote não existe; `Some(None)` se ainda falta avaliar algum job.
+    /// Um lote completo é entregue uma única vez.
+    pub fn results(&mut self, batch_id: Uuid) -> Option<Option<Vec<f64>>> {
+        let batch = self.batches.get(&batch_id)?;
+        if batch.remaining_jobs > 0 {
+            return Some(None);
+        }
+        let batch = self.batches.remove(&batch_id)?;
+        Some(Some(batch.fitnesses.into_iter().flatten().collect()))
+    }
+
+    /// Descarta o lote `batch_id`, abandonado pelo coordenador, e os seus jobs que
+    /// ainda não foram atribuídos. Devolve falso se o lote não existe.
+    pub fn cancel_batch(&mut self, batch_id: Uuid) -> bool {
+        if self.batches.remove(&batch_id).is_none() {
+            return false;
+        }
+        self.pending.retain(|job| job.batch_id != batch_id);
+        true
+    }
+
+    /// Descarta os lotes da task `task_id` e os jobs que ainda não foram atribuídos.
+    pub fn cancel_task(&mut self, task_id: Uuid) {
+        let before = self.batches.len();
+        self.batches.retain(|_, batch| batch.task_id != task_id);
+        if self.batches.len() < before {
+            let batches = &self.batches;
+            self.pending
+                .retain(|job| batches.contains_key(&job.batch_id));
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    convergence::ConvergenceExport, export::ExportSpec, tables::TableSpec,
+    task_manager::DEFAULT_MAX_ATTEMPTS,
+};
+use crate::common::{GraphFormat, GraphModel, IslandModel, Objectives, Problem};
+
+/// Algoritmo a ser executado em todos os grafos, identificado pelo nome sob o
+/// qual os workers o registram, e a variante do problema que ele resolve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlgorithmSpec {
+    pub name: String,
+    /// Nome da configuração no relatório; permite comparar o mesmo algoritmo com
+    /// parâmetros diferentes. Padrão: o nome do algoritmo.
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub problem: Problem,
+    /// Configuração repassada sem alterações ao runner, que a decodifica e valida.
+    #[serde(default)]
+    pub config: serde_json::Value,
+    /// Executa o algoritmo no modelo de ilhas em vez de execuções independentes.
+    #[serde(default)]
+    pub islands: Option<IslandModel>,
+}
+
+impl AlgorithmSpec {
+    #[must_use]
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Conjunto de instâncias sintéticas: `count` grafos do modelo, com as sementes
+/// `seed`, `seed + 1`, ...
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratorSpec {
+    #[serde(flatten)]
+    pub model: GraphModel,
+    #[serde(default = "default_count")]
+    pub count: u32,
+    #[serde(default)]
+    pub seed: u64,
+}
+
+const fn default_count() -> u32 {
+    1
+}
+
+const fn default_max_attempts() -> u32 {
+    DEFAULT_MAX_ATTEMPTS
+}
+
+impl GeneratorSpec {
+    /// Sementes das instâncias do conjunto.
+    pub fn seeds(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..u64::from(self.count)).map(|i| self.seed.wrapping_add(i))
+    }
+}
+
+/// Descrição de um experimento: quantas execuções por grafo e quais algoritmos comparar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Experiment {
+    /// Nome do experimento no banco de resultados; reiniciar o host com o mesmo
+    /// nome retoma as execuções que ainda não terminaram.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub trials: u32,
+    /// Tentativas de cada task antes de ela ser dada como falha. Padrão: 3.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    pub algorithms: Vec<AlgorithmSpec>,
+    /// Se verdadeiro, o host verifica cada solução recebida antes de aceitá-la.
+    #[serde(default)]
+    pub validate_results: bool,
+    /// Formato de todos os arquivos de grafo. Sem ele, o formato de cada arquivo é
+    /// detectado pelo cabeçalho ou pela extensão.
+    #[serde(default)]
+    pub graph_format: Option<GraphFormat>,
+    /// Formato de grafos específicos, pelo nome do arquivo; prevalece sobre `graph_format`.
+    #[serde(default)]
+    pub graph_formats: HashMap<String, GraphFormat>,
+    /// Instâncias sintéticas geradas pelo host antes de distribuir as tasks.
+    #[serde(default)]
+    pub generate: Vec<GeneratorSpec>,
+    /// Sentido de otimização de cada variante, por exemplo `{"roman": "minimize"}`;
+    /// as variantes omitidas são minimizadas.
+    #[serde(default)]
+    pub objectives: Objectives,
+    /// Fitness alvo de cada grafo; a taxa de sucesso é a fração de execuções que o atingem.
+    #[serde(default)]
+    pub targets: HashMap<String, f64>,
+    /// Arquivo com os melhores valores conhecidos de cada instância, atualizado a
+    /// cada recorde; é criado se não existir.
+    #[serde(default)]
+    pub best_known: Option<String>,
+    /// Arquivos CSV ou JSON Lines que recebem cada resultado assim que ele chega.
+    #[serde(default)]
+    pub exports: Vec<ExportSpec>,
+    /// Banco SQLite que guarda as tasks, as tentativas e os resultados do experimento.
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Cópias com data e hora mantidas dos últimos salvamentos periódicos; zero
+    /// mantém apenas o arquivo `save_path`.
+    #[serde(default)]
+    pub keep_saves: usize,
+    /// Tabelas LaTeX ou Markdown geradas ao fim da execução.
+    #[serde(default)]
+    pub tables: Vec<TableSpec>,
+    /// Curvas de convergência médias gravadas ao fim da execução, em CSV ou SVG.
+    #[serde(default)]
+    pub convergence: Vec<ConvergenceExport>,
+}
+
+impl Experiment {
+    /// Nome do experimento no banco de resultados. Padrão: `default`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+
+    /// Formato declarado para o grafo `graph_id`, se houver.
+    #[must_use]
+    pub fn graph_format(&self, graph_id: &str) -> Option<GraphFormat> {
+        self.graph_formats
+            .get(graph_id)
+            .copied()
+            .or(self.graph_format)
+    }
+
+    pub fn from_file(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(file_path)?;
+        let experiment: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Arquivo de experimento '{file_path}' inválido: {e}"))?;
+        experiment.validate()?;
+        Ok(experiment)
+    }
+
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if self.trials == 0 {
+            return Err("O experimento deve ter pelo menos uma execução (trials > 0).".into());
+        }
+        if self.max_attempts == 0 {
+            return Err("Cada tarefa deve ter pelo menos uma tentativa (max_attempts > 0).".into());
+        }
+        if self.algorithms.is_empty() {
+            return Err("O experimento deve declarar pelo menos um algoritmo.".into());
+        }
+        let mut seen = HashSet::new();
+        for algorithm in &self.algorithms {
+            if !seen.insert((algorithm.label(), algorithm.problem)) {
+                return Err(format!(
+                    "Configuração '{}' declarada mais de uma vez para o problema '{}'; use \"label\" para distinguir configurações do mesmo algoritmo.",
+                    algorithm.label(),
+                    algorithm.problem
+                )
+                .into());
+            }
+            if let Some(islands) = &algorithm.islands {
+                islands.validate().map_err(|e| {
+                    format!("Modelo de ilhas inválido para '{}': {e}", algorithm.name)
+                })?;
+            }
+        }
+        for spec in &self.generate {
+            if spec.count == 0 {
+                return Err(format!(
+                    "Gerador {:?} deve produzir pelo menos uma instância (count > 0).",
+                    spec.model
+                )
+                .into());
+            }
+            spec.model
+                .validate()
+                .map_err(|e| format!("Gerador {:?} inválido: {e}", spec.model))?;
+        }
+        for export in &self.exports {
+            if !export.format().is_streaming() {
+                return Err(format!(
+                    "A saída '{}' deve ser CSV ou JSON Lines; o JSON completo é gravado em save_path.",
+                    export.path
+                )
+                .into());
+            }
+        }
+        for export in &self.convergence {
+            if export.points < 2 {
+                return Err(format!(
+                    "As curvas de convergência em '{}' devem ter pelo menos 2 pontos.",
+                    export.path
+                )
+                .into());
+            }
+        }
+        for table in &self.tables {
+            if table.columns.is_empty() {
+                return Err(
+                    format!("A tabela '{}' deve ter pelo menos uma coluna.", table.path).into(),
+                );
+            }
+            let mut seen = HashSet::new();
+            if let Some(configuration) = table
+                .configurations
+                .iter()
+                .find(|configuration| !seen.insert(configuration.as_str()))
+            {
+                return Err(format!(
+                    "Configuração '{configuration}' repetida na tabela '{}'.",
+                    table.path
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Experiment {
+    fn default() -> Self {
+        Self {
+            name: None,
+            trials: 10,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            algorithms: vec![AlgorithmSpec {
+                name: "ga".to_string(),
+                label: None,
+                problem: Problem::default(),
+                config: serde_json::Value::Null,
+                islands: None,
+            }],
+            validate_results: false,
+            graph_format: None,
+            graph_formats: HashMap::new(),
+            generate: Vec::new(),
+            objectives: Objectives::default(),
+            targets: HashMap::new(),
+            best_known: None,
+            exports: Vec::new(),
+            database: None,
+            keep_saves: 0,
+            tables: Vec::new(),
+            convergence: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use std::{
+    borrow::Cow,
+    error::Error,
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::TaskResult;
+
+/// Formato de uma saída de resultados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// JSON aninhado por grafo, reescrito por completo a cada salvamento.
+    Json,
+    /// Uma linha por `TaskResult`, com as colunas de `CSV_HEADER`.
+    Csv,
+    /// Um `TaskResult` completo em JSON por linha.
+    Jsonl,
+}
+
+impl ExportFormat {
+    /// Formato indicado pela extensão de `path` (`.csv`, `.jsonl` ou `.ndjson`);
+    /// qualquer outra extensão é JSON.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => Self::Csv,
+            Some("jsonl" | "ndjson") => Self::Jsonl,
+            _ => Self::Json,
+        }
+    }
+
+    /// Formatos em que cada resultado é acrescentado ao fim do arquivo.
+    #[must_use]
+    pub const fn is_streaming(self) -> bool {
+        matches!(self, Self::Csv | Self::Jsonl)
+    }
+}
+
+/// Saída extra de resultados declarada no experimento.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportSpec {
+    pub path: String,
+    /// Padrão: o formato indicado pela extensão de `path`.
+    #[serde(default)]
+    pub format: Option<ExportFormat>,
+}
+
+impl ExportSpec {
+    #[must_use]
+    pub fn format(&self) -> ExportFormat {
+        self.format
+            .unwrap_or_else(|| ExportFormat::from_path(Path::new(&self.path)))
+    }
+}
+
+pub const CSV_HEADER: &str = "task_id,graph_id,algorithm,configuration,problem,seed,worker_id,fitness,processing_time_ms,interations_run,lower_bound,optimal";
+
+/// Coloca o campo entre aspas quando ele contém vírgulas, aspas ou quebras de linha.
+pub fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+pub fn write_csv_row<W: Write>(writer: &mut W, result: &TaskResult) -> io::Result<()> {
+    writeln!(
+        writer,
+        "{},{},{},{},{},{},{},{},{},{},{},{}",
+        result.task_id,
+        csv_field(&result.graph_id),
+        csv_field(&result.algorithm),
+        csv_field(result.configuration()),
+        result.problem,
+        result.seed,
+        result.worker_id,
+        result.fitness,
+        result.processing_time_ms,
+        result.interations_run,
+        result
+            .lower_bound
+            .map(|bound| bound.to_string())
+            .unwrap_or_default(),
+        result.optimal
+    )
+}
+
+/// Grava `results` em CSV, com o cabeçalho.
+pub fn write_csv<'a, W: Write>(
+    writer: &mut W,
+    results: impl IntoIterator<Item = &'a TaskResult>,
+) -> io::Result<()> {
+    writeln!(writer, "{CSV_HEADER}")?;
+    for result in results {
+        write_csv_row(writer, result)?;
+    }
+    Ok(())
+}
+
+/// Separa o conteúdo de um CSV em registros, respeitando campos entre aspas.
+fn csv_records(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Lê resultados gravados em CSV com as colunas de `CSV_HEADER`, em qualquer ordem.
+/// O CSV não guarda as soluções, então `solution_data` fica vazio.
+pub fn read_csv(content: &str) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+    let mut records = csv_records(content).into_iter();
+    let header = records.next().ok_or("CSV vazio")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| format!("Coluna '{name}' ausente no CSV"))
+    };
+    let task_id = column("task_id")?;
+    let graph_id = column("graph_id")?;
+    let algorithm = column("algorithm")?;
+    let configuration = column("configuration")?;
+    let problem = column("problem")?;
+    let seed = column("seed")?;
+    let worker_id = column("worker_id")?;
+    let fitness = column("fitness")?;
+    let processing_time_ms = column("processing_time_ms")?;
+    let interations_run = column("interations_run")?;
+    let lower_bound = column("lower_bound")?;
+    let optimal = column("optimal")?;
+
+    records
+        .enumerate()
+        .filter(|(_, record)| record.iter().any(|field| !field.is_empty()))
+        .map(|(line, record)| {
+            let field = |index: usize| record.get(index).map_or("", String::as_str);
+            let invalid = |name: &str| format!("Valor inválido em '{name}' na linha {}", line + 2);
+            Ok(TaskResult {
+                task_id: field(task_id).parse().map_err(|_| invalid("task_id"))?,
+                graph_id: field(graph_id).to_string(),
+                algorithm: field(algorithm).to_string(),
+                configuration: field(configuration).to_string(),
+                problem: serde_json::from_value(serde_json::Value::String(
+                    field(problem).to_string(),
+                ))
+                .map_err(|_| invalid("problem"))?,
+                seed: field(seed).parse().map_err(|_| invalid("seed"))?,
+                worker_id: field(worker_id).parse().map_err(|_| invalid("worker_id"))?,
+                fitness: field(fitness).parse().map_err(|_| invalid("fitness"))?,
+                solution_data: Vec::new(),
+                interations_run: field(interations_run)
+                    .parse()
+                    .map_err(|_| invalid("interations_run"))?,
+                processing_time_ms: field(processing_time_ms)
+                    .parse()
+                    .map_err(|_| invalid("processing_time_ms"))?,
+                lower_bound: match field(lower_bound) {
+                    "" => None,
+                    bound => Some(bound.parse().map_err(|_| invalid("lower_bound"))?),
+                },
+                optimal: field(optimal).parse().map_err(|_| invalid("optimal"))?,
+                trace: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Lê um `TaskResult` por linha não vazia.
+pub fn read_jsonl(content: &str) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("Resultado inválido na linha {}: {e}", index + 1).into())
+        })
+        .collect()
+}
+
+/// Grava todos os `results` em `path` de uma vez, substituindo o conteúdo anterior.
+pub fn write_results<'a>(
+    path: &Path,
+    format: ExportFormat,
+    results: impl IntoIterator<Item = &'a TaskResult>,
+) -> Result<(), Box<dyn Error>> {
+    let file =
+        File::create(path).map_err(|e| format!("Falha ao criar '{}': {e}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    match format {
+        ExportFormat::Csv => write_csv(&mut writer, results)?,
+        ExportFormat::Jsonl => {
+            for result in results {
+                write_jsonl_row(&mut writer, result)?;
+            }
+        }
+        ExportFormat::Json => {
+            let results: Vec<&TaskResult> = results.into_iter().collect();
+            serde_json::to_writer_pretty(&mut writer, &results)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn write_jsonl_row<W: Write>(writer: &mut W, result: &TaskResult) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, result)?;
+    writeln!(writer)
+}
+
+/// Arquivo CSV ou JSON Lines que recebe uma linha por resultado, sem reescrever os
+/// anteriores, de modo que o custo de salvar não cresce com o número de resultados.
+pub struct ResultStream {
+    path: PathBuf,
+    format: ExportFormat,
+    writer: BufWriter<File>,
+}
+
+impl ResultStream {
+    /// Abre `path` para acréscimo; o cabeçalho CSV só é escrito em arquivos vazios.
+    pub fn open(path: impl Into<PathBuf>, format: ExportFormat) -> Result<Self, Box<dyn Error>> {
+        let path = path.into();
+        if !format.is_streaming() {
+            return Err(format!(
+                "O formato {format:?} não permite acrescentar resultados a '{}'",
+                path.display()
+            )
+            .into());
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Falha ao abrir '{}': {e}", path.display()))?;
+        let empty = file.metadata()?.len() == 0;
+        let mut writer = BufWriter::new(file);
+        if format == ExportFormat::Csv && empty {
+            writeln!(writer, "{CSV_HEADER}")?;
+            writer.flush()?;
+        }
+        Ok(Self {
+            path,
+            format,
+            writer,
+        })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Acrescenta `result` e descarrega o buffer, para que a linha sobreviva a uma
+    /// queda do host.
+    pub fn append(&mut self, result: &TaskResult) -> io::Result<()> {
+        match self.format {
+            ExportFormat::Csv => write_csv_row(&mut self.writer, result)?,
+            ExportFormat::Jsonl => write_jsonl_row(&mut self.writer, result)?,
+            ExportFormat::Json => unreachable!("ResultStream::open rejeita JSON"),
+        }
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::common::Problem;
+
+    fn result(graph_id: &str, configuration: &str, lower_bound: Option<f64>) -> TaskResult {
+        TaskResult {
+            task_id: Uuid::new_v4(),
+            graph_id: graph_id.to_string(),
+            algorithm: "ga".to_string(),
+            configuration: configuration.to_string(),
+            problem: Problem::DoubleRoman,
+            seed: u64::MAX,
+            worker_id: Uuid::new_v4(),
+            fitness: 12.5,
+            solution_data: vec![2, 0, 1],
+            interations_run: 40,
+            processing_time_ms: 1234,
+            lower_bound,
+            optimal: lower_bound.is_some(),
+            trace: Vec::new(),
+        }
+    }
+
+    fn assert_same(read: &TaskResult, written: &TaskResult) {
+        assert_eq!(read.task_id, written.task_id);
+        assert_eq!(read.graph_id, written.graph_id);
+        assert_eq!(read.algorithm, written.algorithm);
+        assert_eq!(read.configuration, written.configuration);
+        assert_eq!(read.problem, written.problem);
+        assert_eq!(read.seed, written.seed);
+        assert_eq!(read.worker_id, written.worker_id);
+        assert_eq!(read.fitness, written.fitness);
+        assert_eq!(read.processing_time_ms, written.processing_time_ms);
+        assert_eq!(read.interations_run, written.interations_run);
+        assert_eq!(read.lower_bound, written.lower_bound);
+        assert_eq!(read.optimal, written.optimal);
+        assert!(read.solution_data.is_empty());
+    }
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("g.txt"), "g.txt");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("diz \"oi\""), "\"diz \"\"oi\"\"\"");
+        assert_eq!(csv_field("duas\nlinhas"), "\"duas\nlinhas\"");
+    }
+
+    #[test]
+    fn csv_round_trip_keeps_every_column() {
+        let results = vec![
+            result("a,b.txt", "pop \"grande\", elitista", Some(11.0)),
+            result("g.txt", "linha 1\nlinha 2\r\nlinha 3", None),
+            result("\"só aspas\"", "ga", Some(0.1)),
+        ];
+        let mut content = Vec::new();
+        write_csv(&mut content, &results).unwrap();
+        let content = String::from_utf8(content).unwrap();
+
+        let read = read_csv(&content).unwrap();
+        assert_eq!(read.len(), results.len());
+        for (read, written) in read.iter().zip(&results) {
+            assert_same(read, written);
+        }
+
+        // O mesmo arquivo com quebras de linha do Windows.
+        let windows = content.replace("\r\n", "\n").replace('\n', "\r\n");
+        let read = read_csv(&windows).unwrap();
+        assert_eq!(read[0].configuration, results[0].configuration);
+        assert_eq!(read[1].lower_bound, None);
+    }
+
+    #[test]
+    fn csv_columns_can_come_in_any_order() {
+        let written = result("g.txt", "ga-pop50", None);
+        let content = format!(
+            "optimal,extra,lower_bound,fitness,graph_id,task_id,worker_id,seed,problem,configuration,algorithm,interations_run,processing_time_ms\n\
+             false,ignorada,,12.5,g.txt,{},{},{},double_roman,ga-pop50,ga,40,1234\n\n",
+            written.task_id, written.worker_id, written.seed
+        );
+        let read = read_csv(&content).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_same(&read[0], &written);
+    }
+
+    #[test]
+    fn csv_errors_name_the_column() {
+        let error = read_csv("task_id,graph_id\n").unwrap_err();
+        assert_eq!(error.to_string(), "Coluna 'algorithm' ausente no CSV");
+
+        let mut content = Vec::new();
+        write_csv(&mut content, &[result("g.txt", "ga", None)]).unwrap();
+        let content = String::from_utf8(content).unwrap().replace("12.5", "doze");
+        let error = read_csv(&content).unwrap_err();
+        assert_eq!(error.to_string(), "Valor inválido em 'fitness' na linha 2");
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+
+use log::info;
+use rand::Rng;
+
+use crate::common::{IslandModel, Migrant, Objective, TaskResult, Topology};
+
+/// Estado de um grupo de ilhas no host: os imigrantes à espera de cada ilha e os
+/// resultados das ilhas que já terminaram.
+#[derive(Debug, Clone)]
+pub struct IslandGroup {
+    model: IslandModel,
+    mailboxes: Vec<VecDeque<Migrant>>,
+    results: Vec<Option<TaskResult>>,
+}
+
+impl IslandGroup {
+    #[must_use]
+    pub fn new(model: IslandModel) -> Self {
+        let islands = model.islands as usize;
+        Self {
+            model,
+            mailboxes: vec![VecDeque::new(); islands],
+            results: vec![None; islands],
+        }
+    }
+
+    fn destinations(&self, island: usize) -> Vec<usize> {
+        let islands = self.mailboxes.len();
+        match self.model.topology {
+            Topology::Ring => vec![(island + 1) % islands],
+            Topology::FullyConnected => (0..islands).filter(|&other| other != island).collect(),
+            Topology::Random => {
+                let other = rand::rng().random_range(0..islands - 1);
+                vec![if other >= island { other + 1 } else { other }]
+            }
+        }
+    }
+
+    /// Entrega `emigrants` às ilhas vizinhas de `island` e devolve os imigrantes de `island`.
+    ///
+    /// Cada caixa guarda apenas os imigrantes mais recentes, no máximo um lote por
+    /// ilha de origem, para que uma ilha lenta não acumule indivíduos antigos.
+    pub fn migrate(&mut self, island: u32, emigrants: Vec<Migrant>) -> Vec<Migrant> {
+        let island = island as usize;
+        let capacity = self.model.migrants as usize * (self.mailboxes.len() - 1);
+        for destination in self.destinations(island) {
+            let mailbox = &mut self.mailboxes[destination];
+            mailbox.extend(emigrants.iter().cloned());
+            while mailbox.len() > capacity {
+                mailbox.pop_front();
+            }
+        }
+        self.mailboxes[island].drain(..).collect()
+    }
+
+    /// Registra o resultado de `island`. Quando todas as ilhas terminam, devolve o
+    /// melhor resultado do grupo segundo `objective`, que conta como uma única
+    /// execução do algoritmo.
+    pub fn complete(
+        &mut self,
+        island: u32,
+        result: TaskResult,
+        objective: Objective,
+    ) -> Option<TaskResult> {
+        self.results[island as usize] = Some(result);
+        if self.results.iter().any(Option::is_none) {
+            return None;
+        }
+        let best = self
+            .results
+            .iter_mut()
+            .filter_map(Option::take)
+            .min_by(|a, b| objective.compare(a.fitness, b.fitness))?;
+        info!(
+            "Grupo de {} ilhas do grafo '{}' finalizado com fitness {}",
+            self.model.islands, best.graph_id, best.fitness
+        );
+        Some(best)
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+use log::{info, warn};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{
+    catalog::InstanceCatalog,
+    export::{self, ExportFormat},
+    periodic_saver::{SaverResults, SaverSnapshot},
+};
+use crate::common::{InstanceStats, TaskResult};
+
+/// Parte de um relatório salvo necessária para reconstruir as estatísticas.
+#[derive(Deserialize)]
+struct SavedReport {
+    graphs: HashMap<String, SavedGraph>,
+}
+
+#[derive(Deserialize)]
+struct SavedGraph {
+    #[serde(default)]
+    instance: Option<InstanceStats>,
+    results: Vec<TaskResult>,
+}
+
+/// Arquivo JSON gravado pelo host: o relatório final ou o salvamento periódico, no
+/// formato atual ou no antigo, que era só a lista de resultados por grafo.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedJson {
+    Report(SavedReport),
+    Periodic(SaverSnapshot),
+    LegacyPeriodic(Vec<SaverResults>),
+}
+
+fn is_database(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("sqlite" | "sqlite3" | "db")
+    )
+}
+
+/// Resultados reunidos de vários arquivos salvos, sem repetir tasks, e as instâncias
+/// descritas nos relatórios.
+#[derive(Debug, Default)]
+pub struct ResultSet {
+    results: Vec<TaskResult>,
+    task_ids: HashMap<Uuid, usize>,
+    catalog: InstanceCatalog,
+    duplicates: usize,
+}
+
+impl ResultSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Acrescenta os resultados de `path`, reconhecido pela extensão: `.csv`, `.jsonl`
+    /// ou `.ndjson`, banco SQLite (`.sqlite`, `.sqlite3` ou `.db`, do qual são lidos
+    /// os resultados do experimento `experiment_name`) ou JSON, que pode ser um
+    /// relatório final ou um arquivo de salvamento periódico. Devolve quantos
+    /// resultados novos foram lidos.
+    pub fn load(&mut self, path: &str, experiment_name: &str) -> Result<usize, Box<dyn Error>> {
+        let file_path = Path::new(path);
+        let results = if is_database(file_path) {
+            Self::read_database(path, experiment_name)?
+        } else {
+            let content =
+                fs::read_to_string(file_path).map_err(|e| format!("Falha ao ler '{path}': {e}"))?;
+            match ExportFormat::from_path(file_path) {
+                ExportFormat::Csv => export::read_csv(&content),
+                ExportFormat::Jsonl => export::read_jsonl(&content),
+                ExportFormat::Json => self.read_json(&content),
+            }
+            .map_err(|e| format!("Arquivo de resultados '{path}' inválido: {e}"))?
+        };
+
+        let before = self.results.len();
+        for result in results {
+            self.push(result);
+        }
+        let added = self.results.len() - before;
+        info!("{added} resultados novos lidos de '{path}'");
+        Ok(added)
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn read_database(path: &str, experiment_name: &str) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+        super::sqlite_store::SqliteStore::read_results(path, experiment_name)
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    fn read_database(
+        path: &str,
+        _experiment_name: &str,
+    ) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+        Err(
+            format!("Sem suporte a SQLite para ler '{path}': compile com a feature 'sqlite'")
+                .into(),
+        )
+    }
+
+    fn read_json(&mut self, content: &str) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+        match serde_json::from_str(content)? {
+            SavedJson::Report(report) => {
+                let mut graphs: Vec<(String, SavedGraph)> = report.graphs.into_iter().collect();
+                graphs.sort_by(|(a, _), (b, _)| a.cmp(b));
+                let mut results = Vec::new();
+                for (graph_id, graph) in graphs {
+                    if let Some(stats) = graph.instance
+                        && self.catalog.get(&graph_id).is_none()
+                    {
+                        self.catalog.insert(&graph_id, stats);
+                    }
+                    results.extend(graph.results);
+                }
+                Ok(results)
+            }
+            SavedJson::Periodic(SaverSnapshot {
+                results: graphs, ..
+            })
+            | SavedJson::LegacyPeriodic(graphs) => Ok(graphs
+                .into_iter()
+                .flat_map(|graph| {
+                    let name = graph.name;
+                    graph
+                        .results
+                        .into_iter()
+                        .map(move |result| result.into_result(&name))
+                })
+                .collect()),
+        }
+    }
+
+    /// Acrescenta `result`, a menos que a sua task já tenha um resultado. Nesse caso,
+    /// a solução e a curva de convergência que faltarem ao resultado mantido são
+    /// copiadas de `result`, já que o CSV não as guarda e pode ser lido antes do JSON.
+    /// Devolve verdadeiro se o resultado foi acrescentado.
+    pub fn push(&mut self, result: TaskResult) -> bool {
+        if let Some(&index) = self.task_ids.get(&result.task_id) {
+            let previous = &mut self.results[index];
+            if previous.fitness != result.fitness || previous.graph_id != result.graph_id {
+                warn!(
+                    "Task {} aparece com resultados diferentes ({} em '{}' e {} em '{}'); mantendo o primeiro",
+                    result.task_id,
+                    previous.fitness,
+                    previous.graph_id,
+                    result.fitness,
+                    result.graph_id
+                );
+            } else {
+                if previous.solution_data.is_empty() {
+                    previous.solution_data = result.solution_data;
+                }
+                if previous.trace.is_empty() {
+                    previous.trace = result.trace;
+                }
+            }
+            self.duplicates += 1;
+            return false;
+        }
+        self.task_ids.insert(result.task_id, self.results.len());
+        self.results.push(result);
+        true
+    }
+
+    #[must_use]
+    pub fn results(&self) -> &[TaskResult] {
+        &self.results
+    }
+
+    /// Instâncias descritas nos relatórios lidos.
+    #[must_use]
+    pub const fn catalog(&self) -> &InstanceCatalog {
+        &self.catalog
+    }
+
+    /// Resultados ignorados por repetirem uma task já lida.
+    #[must_use]
+    pub const fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    /// Grafos com resultados.
+    #[must_use]
+    pub fn graphs(&self) -> HashSet<&str> {
+        self.results.iter().map(|r| r.graph_id.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Problem, TracePoint};
+
+    fn result(fitness: f64) -> TaskResult {
+        TaskResult {
+            task_id: Uuid::new_v4(),
+            graph_id: "g.txt".to_string(),
+            algorithm: "ga".to_string(),
+            configuration: String::new(),
+            problem: Problem::Roman,
+            seed: 7,
+            worker_id: Uuid::new_v4(),
+            fitness,
+            solution_data: vec![2, 0, 1],
+            interations_run: 10,
+            processing_time_ms: 5,
+            lower_bound: None,
+            optimal: false,
+            trace: vec![TracePoint {
+                elapsed_ms: 1.0,
+                iteration: 1,
+                best_fitness: fitness,
+            }],
+        }
+    }
+
+    fn report(results: &[TaskResult]) -> String {
+        serde_json::json!({ "graphs": { "g.txt": { "results": results } } }).to_string()
+    }
+
+    #[test]
+    fn json_read_after_csv_fills_solution_and_trace() {
+        let original = vec![result(3.0), result(4.0)];
+        let mut csv = Vec::new();
+        export::write_csv(&mut csv, &original).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        let mut set = ResultSet::new();
+        for result in export::read_csv(&csv).unwrap() {
+            assert!(result.solution_data.is_empty() && result.trace.is_empty());
+            assert!(set.push(result));
+        }
+        for result in set.read_json(&report(&original)).unwrap() {
+            assert!(!set.push(result));
+        }
+
+        assert_eq!(set.duplicates(), 2);
+        assert_eq!(set.results().len(), 2);
+        for (merged, original) in set.results().iter().zip(&original) {
+            assert_eq!(merged.task_id, original.task_id);
+            assert_eq!(merged.solution_data, original.solution_data);
+            assert_eq!(merged.trace, original.trace);
+        }
+    }
+
+    #[test]
+    fn conflicting_duplicate_keeps_first_result() {
+        let first = result(3.0);
+        let mut second = result(5.0);
+        second.task_id = first.task_id;
+        second.solution_data = vec![1, 1, 1];
+
+        let mut set = ResultSet::new();
+        assert!(set.push(first.clone()));
+        assert!(!set.push(second));
+        assert_eq!(set.results()[0].fitness, first.fitness);
+        assert_eq!(set.results()[0].solution_data, first.solution_data);
+    }
+}
@@ -1,4 +1,19 @@
+pub mod best_known;
+pub mod catalog;
+pub mod comparison;
+pub mod convergence;
+pub mod evaluation;
+pub mod experiment;
+pub mod export;
+pub mod island;
+pub mod merge;
 pub mod periodic_saver;
 pub mod result_aggregator;
 pub mod server;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store;
+pub mod statistics;
+pub mod store;
+pub mod tables;
 pub mod task_manager;
+pub mod validator;
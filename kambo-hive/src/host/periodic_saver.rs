@@ -1,82 +1,380 @@
-use std::fs;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 
-use log::{error, info};
-use serde::Serialize;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::result_aggregator::ResultAggregator;
+use super::task_manager::{TaskManager, TaskStatus};
+use crate::common::{Problem, TaskResult, TracePoint};
 
-#[derive(Serialize, Clone)]
-struct SaverTaskResult {
+/// Resultado no arquivo de salvamento periódico.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SaverTaskResult {
     pub task_id: Uuid,
+    /// Ausente nos arquivos antigos, em que o grafo fica só em `SaverResults::name`.
+    #[serde(default)]
+    pub graph_id: String,
+    #[serde(default)]
+    pub algorithm: String,
+    #[serde(default)]
+    pub configuration: String,
+    #[serde(default)]
+    pub problem: Problem,
+    #[serde(default)]
+    pub seed: u64,
     pub worker_id: Uuid,
     pub fitness: f64,
     pub solution_data: Vec<u8>,
     pub interations_run: u32,
     pub processing_time_ms: u64,
+    #[serde(default)]
+    pub lower_bound: Option<f64>,
+    #[serde(default)]
+    pub optimal: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<TracePoint>,
 }
 
-#[derive(Serialize)]
-struct SaverResults {
-    name: String,
-    results: Vec<SaverTaskResult>,
+impl SaverTaskResult {
+    fn new(result: &TaskResult) -> Self {
+        Self {
+            task_id: result.task_id,
+            graph_id: result.graph_id.clone(),
+            algorithm: result.algorithm.clone(),
+            configuration: result.configuration.clone(),
+            problem: result.problem,
+            seed: result.seed,
+            worker_id: result.worker_id,
+            fitness: result.fitness,
+            solution_data: result.solution_data.clone(),
+            interations_run: result.interations_run,
+            processing_time_ms: result.processing_time_ms,
+            lower_bound: result.lower_bound,
+            optimal: result.optimal,
+            trace: result.trace.clone(),
+        }
+    }
+
+    /// Converte de volta em `TaskResult`; `graph_id` vale para arquivos antigos, em
+    /// que o resultado não traz o próprio grafo.
+    #[must_use]
+    pub fn into_result(self, graph_id: &str) -> TaskResult {
+        TaskResult {
+            task_id: self.task_id,
+            graph_id: if self.graph_id.is_empty() {
+                graph_id.to_string()
+            } else {
+                self.graph_id
+            },
+            algorithm: self.algorithm,
+            configuration: self.configuration,
+            problem: self.problem,
+            seed: self.seed,
+            worker_id: self.worker_id,
+            fitness: self.fitness,
+            solution_data: self.solution_data,
+            interations_run: self.interations_run,
+            processing_time_ms: self.processing_time_ms,
+            lower_bound: self.lower_bound,
+            optimal: self.optimal,
+            trace: self.trace,
+        }
+    }
+}
+
+/// Resultados de um grafo no arquivo de salvamento periódico.
+#[derive(Serialize, Deserialize)]
+pub struct SaverResults {
+    pub name: String,
+    pub results: Vec<SaverTaskResult>,
+}
+
+/// Task ainda não concluída no momento do salvamento.
+#[derive(Serialize, Deserialize)]
+pub struct SaverTask {
+    pub task_id: Uuid,
+    pub graph_id: String,
+    pub algorithm: String,
+    pub configuration: String,
+    pub problem: Problem,
+    pub run_number: u32,
+    pub status: TaskStatus,
+    /// Tentativas que já falharam.
+    #[serde(default)]
+    pub failed_attempts: u32,
+    /// Worker que executa a task, se ela estiver atribuída.
+    pub worker_id: Option<Uuid>,
+}
+
+/// Conteúdo do arquivo de salvamento periódico.
+#[derive(Serialize, Deserialize)]
+pub struct SaverSnapshot {
+    /// Data e hora do salvamento, em UTC.
+    pub saved_at: String,
+    pub total_tasks: usize,
+    pub completed_tasks: usize,
+    pub results: Vec<SaverResults>,
+    /// Tasks pendentes, atribuídas ou que falharam em todas as tentativas.
+    pub tasks: Vec<SaverTask>,
 }
 
-pub fn start(aggregator: Arc<Mutex<ResultAggregator>>, file_path: String, interval_secs: u64) {
-    info!("Salvamento periódico ativado. Arquivo: '{file_path}', Intervalo: {interval_secs}s.");
+/// Data e hora UTC de `time` como (ano, mês, dia, hora, minuto, segundo).
+fn utc(time: SystemTime) -> (i64, u32, u32, u64, u64, u64) {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, time_of_day) = (seconds / 86_400, seconds % 86_400);
+    // Conversão de dias desde 1970-01-01 para o calendário civil (Howard Hinnant).
+    let z = days.cast_signed() + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (
+        year,
+        month,
+        day,
+        time_of_day / 3600,
+        time_of_day % 3600 / 60,
+        time_of_day % 60,
+    )
+}
 
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+/// Marca de tempo compacta usada no nome das cópias, por exemplo `20261019T005155Z`.
+fn compact_timestamp(time: SystemTime) -> String {
+    let (year, month, day, hour, minute, second) = utc(time);
+    format!("{year:04}{month:02}{day:02}T{hour:02}{minute:02}{second:02}Z")
+}
 
-        loop {
-            interval.tick().await;
-            let results_guard = aggregator.lock().await;
+fn iso_timestamp(time: SystemTime) -> String {
+    let (year, month, day, hour, minute, second) = utc(time);
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
 
-            if results_guard.get_results_collected() == 0 {
-                info!("Nenhum resultado para salvar, pulando ciclo de salvamento.");
-                continue;
+/// Grava `content` em um arquivo temporário ao lado de `path`, força a gravação no
+/// disco e o renomeia, para que uma falha no meio da escrita ou uma queda de energia
+/// não deixe um arquivo truncado. Quem chama garante que só uma escrita por `path`
+/// ocorre de cada vez, já que o temporário tem nome fixo.
+pub fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut temporary = path.to_path_buf().into_os_string();
+    temporary.push(".tmp");
+    let mut file = File::create(&temporary)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temporary, path)
+}
+
+/// Salva os resultados e o estado das tasks em um arquivo JSON, opcionalmente
+/// mantendo cópias com data e hora dos últimos salvamentos.
+#[derive(Clone)]
+pub struct PeriodicSaver {
+    path: PathBuf,
+    keep: usize,
+    task_manager: Arc<Mutex<TaskManager>>,
+    aggregator: Arc<Mutex<ResultAggregator>>,
+    /// Serializa os salvamentos do ciclo periódico e os pedidos diretos, como o do
+    /// Ctrl+C, que escrevem no mesmo arquivo temporário.
+    writing: Arc<Mutex<()>>,
+}
+
+impl PeriodicSaver {
+    /// `keep` é o número de cópias com data e hora mantidas ao lado de `path`; zero
+    /// desativa as cópias.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        keep: usize,
+        task_manager: Arc<Mutex<TaskManager>>,
+        aggregator: Arc<Mutex<ResultAggregator>>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            keep,
+            task_manager,
+            aggregator,
+            writing: Arc::new(Mutex::new(())),
+        }
+    }
+
+    async fn snapshot(&self) -> SaverSnapshot {
+        let tm = self.task_manager.lock().await;
+        let aggregator = self.aggregator.lock().await;
+
+        let mut results: Vec<SaverResults> = aggregator
+            .get_all_results()
+            .iter()
+            .map(|(graph_name, task_results)| SaverResults {
+                name: graph_name.clone(),
+                results: task_results.iter().map(SaverTaskResult::new).collect(),
+            })
+            .collect();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut tasks: Vec<SaverTask> = tm
+            .unfinished_tasks()
+            .map(|(task, status, worker_id)| SaverTask {
+                task_id: task.id,
+                graph_id: task.graph_id.clone(),
+                algorithm: task.algorithm.clone(),
+                configuration: task.configuration().to_string(),
+                problem: task.problem,
+                run_number: task.run_number,
+                status,
+                failed_attempts: tm.failed_attempts(task.id),
+                worker_id,
+            })
+            .collect();
+        tasks.sort_by(|a, b| {
+            (&a.graph_id, &a.configuration, a.problem, a.run_number).cmp(&(
+                &b.graph_id,
+                &b.configuration,
+                b.problem,
+                b.run_number,
+            ))
+        });
+
+        SaverSnapshot {
+            saved_at: iso_timestamp(SystemTime::now()),
+            total_tasks: tm.get_total_tasks(),
+            completed_tasks: tm.get_completed_tasks_count(),
+            results,
+            tasks,
+        }
+    }
+
+    /// Salva o estado atual e devolve quantos resultados foram gravados.
+    pub async fn save(&self) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        // Tomado antes do retrato, para que o último salvamento grave o estado mais novo,
+        // e solto só ao fim da escrita, mesmo que este futuro seja abortado antes.
+        let writing = Arc::clone(&self.writing).lock_owned().await;
+        let snapshot = self.snapshot().await;
+        let count = snapshot.results.iter().map(|r| r.results.len()).sum();
+        let json_data = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| format!("Falha ao serializar resultados para JSON: {e}"))?;
+        // A escrita e o fsync bloqueiam; ficam fora das threads do runtime.
+        let saver = self.clone();
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let _writing = writing;
+            write_atomic(&saver.path, &json_data).map_err(|e| {
+                format!(
+                    "Falha ao escrever no arquivo de resultados '{}': {e}",
+                    saver.path.display()
+                )
+            })?;
+            if saver.keep > 0 {
+                saver.rotate(&json_data);
             }
+            Ok(())
+        })
+        .await
+        .map_err(|e| format!("Falha na tarefa de salvamento: {e}"))??;
+        Ok(count)
+    }
+
+    /// Partes do nome das cópias antes e depois da marca de tempo: para `results.json`,
+    /// as cópias se chamam `results.20261019T005155Z.json`.
+    fn copy_affixes(&self) -> (String, String) {
+        let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+        let suffix = self
+            .path
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+        (format!("{stem}."), suffix)
+    }
+
+    /// Grava a cópia com data e hora do salvamento e apaga as mais antigas além de `keep`.
+    fn rotate(&self, json_data: &str) {
+        let (prefix, suffix) = self.copy_affixes();
+        let timestamp = compact_timestamp(SystemTime::now());
+        let copy = self
+            .path
+            .with_file_name(format!("{prefix}{timestamp}{suffix}"));
+        if let Err(e) = write_atomic(&copy, json_data) {
+            warn!("Falha ao gravar a cópia '{}': {e}", copy.display());
+            return;
+        }
 
-            info!(
-                "Preparando {} resultados para salvar em '{}'...",
-                results_guard.get_results_collected(),
-                file_path
-            );
-
-            let formatted_results: Vec<SaverResults> = results_guard
-                .get_all_results()
-                .iter()
-                .map(|(graph_name, task_results)| SaverResults {
-                    name: graph_name.clone(),
-                    results: task_results
-                        .iter()
-                        .map(|tr| SaverTaskResult {
-                            task_id: tr.task_id,
-                            worker_id: tr.worker_id,
-                            fitness: tr.fitness,
-                            solution_data: tr.solution_data.clone(),
-                            interations_run: tr.interations_run,
-                            processing_time_ms: tr.processing_time_ms,
-                        })
-                        .collect(),
-                })
-                .collect();
-
-            match serde_json::to_string_pretty(&formatted_results) {
-                Ok(json_data) => {
-                    if let Err(e) = fs::write(&file_path, json_data) {
-                        error!("Falha ao escrever no arquivo de resultados '{file_path}': {e}");
-                    } else {
-                        info!("Resultados salvos com sucesso.");
-                    }
+        let directory = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!(
+                    "Falha ao listar as cópias em '{}': {e}",
+                    directory.display()
+                );
+                return;
+            }
+        };
+        // A marca de tempo tem largura fixa, então a ordem dos nomes é a cronológica.
+        let mut copies: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.strip_prefix(&prefix))
+                    .and_then(|name| name.strip_suffix(&suffix))
+                    .is_some_and(|timestamp| {
+                        timestamp.len() == 16
+                            && timestamp.as_bytes()[8] == b'T'
+                            && timestamp.ends_with('Z')
+                    })
+            })
+            .collect();
+        copies.sort();
+        let excess = copies.len().saturating_sub(self.keep);
+        for old in &copies[..excess] {
+            if let Err(e) = fs::remove_file(old) {
+                warn!("Falha ao apagar a cópia antiga '{}': {e}", old.display());
+            }
+        }
+    }
+
+    /// Salva a cada `interval_secs` segundos, pulando os ciclos sem resultados. O
+    /// ciclo deve ser abortado pelo handle devolvido antes do salvamento final.
+    pub fn start(&self, interval_secs: u64) -> tokio::task::JoinHandle<()> {
+        info!(
+            "Salvamento periódico ativado. Arquivo: '{}', Intervalo: {interval_secs}s, Cópias mantidas: {}.",
+            self.path.display(),
+            self.keep
+        );
+        let saver = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+            loop {
+                interval.tick().await;
+                if saver.aggregator.lock().await.get_results_collected() == 0 {
+                    info!("Nenhum resultado para salvar, pulando ciclo de salvamento.");
+                    continue;
                 }
-                Err(e) => {
-                    error!("Falha ao serializar resultados para JSON: {e}");
+
+                match saver.save().await {
+                    Ok(count) => info!(
+                        "{count} resultados salvos com sucesso em '{}'.",
+                        saver.path.display()
+                    ),
+                    Err(e) => error!("{e}"),
                 }
             }
-        }
-    });
+        })
+    }
 }
@@ -1,17 +1,116 @@
-use log::info;
+use log::{error, info};
 use serde::Serialize;
 use std::{collections::HashMap, error::Error, fs};
 use uuid::Uuid;
 
-use super::task_manager::{TaskManager, TaskStatus};
-use crate::common::TaskResult;
+use super::{
+    best_known::{BestKnown, BestKnownRegistry, NewRecord},
+    catalog::InstanceCatalog,
+    comparison::{self, ProblemComparison},
+    convergence::{self, CurvePoint, DEFAULT_CURVE_POINTS},
+    export::ResultStream,
+    statistics::{GraphStatistics, RunStatistics, Summary},
+    task_manager::{TaskManager, TaskStatus},
+};
+use crate::common::{InstanceStats, Objective, Objectives, Problem, TaskResult};
 
+/// Execuções comparadas com o melhor valor conhecido da instância.
+#[derive(Serialize)]
+struct ReportBestKnown {
+    fitness: f64,
+    optimal: bool,
+    /// Gap percentual da melhor execução e da fitness média.
+    best_gap_percent: Option<f64>,
+    mean_gap_percent: Option<f64>,
+    /// Execuções que atingiram o melhor valor conhecido.
+    hits: usize,
+    /// Tempo de processamento das execuções que o atingiram.
+    time_to_target_ms: Option<Summary>,
+}
+
+impl ReportBestKnown {
+    fn new(runs: &RunStatistics, known: &BestKnown, objective: Objective) -> Self {
+        let gap = |fitness: f64| objective.gap_percent(fitness, known.fitness);
+        Self {
+            fitness: known.fitness,
+            optimal: known.optimal,
+            best_gap_percent: objective
+                .best(runs.fitness.values().iter().copied())
+                .and_then(gap),
+            mean_gap_percent: runs.fitness.summary().and_then(|summary| gap(summary.mean)),
+            hits: runs.fitness.count_reaching(known.fitness, objective),
+            time_to_target_ms: runs.time_to_target(known.fitness, objective).summary(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ReportRunStatistics {
+    /// Fração das execuções que atingiram o alvo do grafo, se houver um.
+    success_rate: Option<f64>,
+    fitness: Option<Summary>,
+    processing_time_ms: Option<Summary>,
+    best_known: Option<ReportBestKnown>,
+}
+
+impl ReportRunStatistics {
+    fn new(
+        runs: &RunStatistics,
+        target: Option<f64>,
+        best_known: Option<&BestKnown>,
+        objective: Objective,
+    ) -> Self {
+        Self {
+            success_rate: target.and_then(|target| runs.success_rate(target, objective)),
+            fitness: runs.fitness.summary(),
+            processing_time_ms: runs.processing_time_ms.summary(),
+            best_known: best_known.map(|known| ReportBestKnown::new(runs, known, objective)),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ReportConfiguration {
+    configuration: String,
+    algorithm: String,
+    problem: Problem,
+    objective: Objective,
+    best_fitness: Option<f64>,
+    #[serde(flatten)]
+    statistics: ReportRunStatistics,
+    /// Curva de convergência média, se as execuções a registraram.
+    convergence: Option<Vec<CurvePoint>>,
+}
+
+/// `objective`, `best_fitness`, `best_result`, `lower_bound` e `gap_percent` só são
+/// preenchidos quando todos os resultados do grafo são da mesma variante do problema,
+/// já que fitness de variantes diferentes não são comparáveis.
 #[derive(Serialize)]
 struct ReportGraphDetails {
+    instance: Option<InstanceStats>,
     results_collected: usize,
-    best_fitness: f64,
+    objective: Option<Objective>,
+    best_fitness: Option<f64>,
+    /// Resultado completo, com a solução, que obteve `best_fitness`.
+    best_result: Option<TaskResult>,
+    /// Limite mais apertado reportado pelos solvers (ou o ótimo, se algum resultado
+    /// for ótimo): inferior ao minimizar e superior ao maximizar.
+    lower_bound: Option<f64>,
+    /// Gap percentual entre `best_fitness` e `lower_bound`.
+    gap_percent: Option<f64>,
+    /// Fitness alvo declarado no experimento.
+    target: Option<f64>,
+    /// Algum resultado deste host melhorou o melhor valor conhecido do grafo.
+    new_record: bool,
+    /// Estatísticas de todas as execuções do grafo; a fitness só é resumida quando os
+    /// resultados são da mesma variante do problema.
+    #[serde(flatten)]
+    statistics: ReportRunStatistics,
+    /// As mesmas estatísticas para cada configuração executada no grafo.
+    configurations: Vec<ReportConfiguration>,
     avg_processing_time_ms: f64,
     total_processing_time_ms: u64,
+    /// Resultados ordenados do melhor para o pior.
     results: Vec<TaskResult>,
 }
 
@@ -37,23 +136,113 @@ struct JsonReport {
     task_summary: ReportStatusSummary,
     graphs: HashMap<String, ReportGraphDetails>,
     workers: Vec<WorkerReport>, // Novo campo para estatísticas dos workers
+    /// Testes estatísticos entre as configurações de cada variante do problema.
+    comparison: Vec<ProblemComparison>,
+    /// Melhores valores conhecidos superados pelos resultados deste host.
+    new_records: Vec<NewRecord>,
 }
 
 pub struct ResultAggregator {
     results_by_graph: HashMap<String, Vec<TaskResult>>,
+    best_results: HashMap<(String, Problem), TaskResult>, // (GraphId, variante) -> melhor resultado
+    statistics: HashMap<String, GraphStatistics>,
+    objectives: Objectives,
+    targets: HashMap<String, f64>, // GraphId -> fitness alvo
+    best_known: Option<BestKnownRegistry>,
+    streams: Vec<ResultStream>,
     total_results_collected: usize,
 }
 
 impl ResultAggregator {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_objectives(Objectives::default())
+    }
+
+    #[must_use]
+    pub fn with_objectives(objectives: Objectives) -> Self {
         Self {
             results_by_graph: HashMap::new(),
+            best_results: HashMap::new(),
+            statistics: HashMap::new(),
+            objectives,
+            targets: HashMap::new(),
+            best_known: None,
+            streams: Vec::new(),
             total_results_collected: 0,
         }
     }
 
+    #[must_use]
+    pub const fn objectives(&self) -> &Objectives {
+        &self.objectives
+    }
+
+    /// Define a fitness alvo de cada grafo, usada na taxa de sucesso.
+    pub fn set_targets(&mut self, targets: HashMap<String, f64>) {
+        self.targets = targets;
+    }
+
+    /// Passa a comparar os resultados com `registry` e a registrar nela os novos recordes.
+    pub fn set_best_known(&mut self, registry: BestKnownRegistry) {
+        self.best_known = Some(registry);
+    }
+
+    #[must_use]
+    pub const fn best_known(&self) -> Option<&BestKnownRegistry> {
+        self.best_known.as_ref()
+    }
+
+    /// Passa a acrescentar cada resultado recebido a `stream`.
+    pub fn add_stream(&mut self, stream: ResultStream) {
+        info!(
+            "Resultados serão acrescentados a '{}'",
+            stream.path().display()
+        );
+        self.streams.push(stream);
+    }
+
+    /// Estatísticas de cada grafo, atualizadas a cada resultado.
+    #[must_use]
+    pub const fn statistics(&self) -> &HashMap<String, GraphStatistics> {
+        &self.statistics
+    }
+
     pub fn add_result(&mut self, result: TaskResult) -> Result<(), Box<dyn Error>> {
+        let objective = self.objectives.get(result.problem);
+        let key = (result.graph_id.clone(), result.problem);
+        match self.best_results.get(&key) {
+            Some(best) if !objective.is_better(result.fitness, best.fitness) => {}
+            previous => {
+                info!(
+                    "Nova melhor fitness para '{}' ({}): {} (anterior: {})",
+                    result.graph_id,
+                    result.problem,
+                    result.fitness,
+                    previous.map_or_else(|| "nenhuma".to_string(), |best| best.fitness.to_string())
+                );
+                self.best_results.insert(key, result.clone());
+            }
+        }
+
+        if let Some(registry) = &mut self.best_known {
+            registry.offer(&result, objective);
+        }
+        for stream in &mut self.streams {
+            if let Err(e) = stream.append(&result) {
+                error!(
+                    "Falha ao acrescentar o resultado da task {} a '{}': {e}",
+                    result.task_id,
+                    stream.path().display()
+                );
+            }
+        }
+
+        self.statistics
+            .entry(result.graph_id.clone())
+            .or_default()
+            .push(&result);
+
         let graph_id = result.graph_id.clone();
         self.results_by_graph
             .entry(graph_id)
@@ -84,8 +273,6 @@ impl ResultAggregator {
         task_manager: &TaskManager,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
-        info!("Gerando relatório final para {}", file_path);
-
         let task_summary = ReportStatusSummary {
             total: task_manager.get_total_tasks(),
             completed: task_manager.get_completed_tasks_count(),
@@ -105,6 +292,38 @@ impl ResultAggregator {
                 .filter(|&&s| s == TaskStatus::Assigned)
                 .count(),
         };
+        self.save_report(task_summary, task_manager.catalog(), file_path)
+    }
+
+    /// Relatório de resultados lidos de arquivos salvos, sem um `TaskManager`: todos
+    /// os resultados contam como tasks concluídas.
+    pub fn save_offline_report(
+        &self,
+        catalog: &InstanceCatalog,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let task_summary = ReportStatusSummary {
+            total: self.total_results_collected,
+            completed: self.total_results_collected,
+            failed: 0,
+            pending: 0,
+            assigned: 0,
+        };
+        self.save_report(task_summary, catalog, file_path)
+    }
+
+    fn save_report(
+        &self,
+        task_summary: ReportStatusSummary,
+        catalog: &InstanceCatalog,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        info!("Gerando relatório final para {}", file_path);
+
+        let new_records = self
+            .best_known
+            .as_ref()
+            .map_or(&[][..], BestKnownRegistry::records);
 
         let graphs: HashMap<String, ReportGraphDetails> = self
             .get_all_results()
@@ -116,19 +335,91 @@ impl ResultAggregator {
                 } else {
                     total_time_ms as f64 / results.len() as f64
                 };
-                let best_fitness = results
+                let problem = results
+                    .first()
+                    .map(|r| r.problem)
+                    .filter(|&problem| results.iter().all(|r| r.problem == problem));
+                let objective = problem.map(|problem| self.objectives.get(problem));
+                let best_result = problem
+                    .and_then(|problem| self.best_results.get(&(graph_id.clone(), problem)))
+                    .cloned();
+                let best_fitness = best_result.as_ref().map(|r| r.fitness);
+                let lower_bound = objective.and_then(|objective| {
+                    objective.tightest_bound(results.iter().filter_map(|r| {
+                        if r.optimal {
+                            Some(r.fitness)
+                        } else {
+                            r.lower_bound
+                        }
+                    }))
+                });
+                let gap_percent = objective
+                    .zip(best_fitness)
+                    .zip(lower_bound)
+                    .and_then(|((objective, best), bound)| objective.gap_percent(best, bound));
+
+                let target = self.targets.get(graph_id).copied();
+                let graph_statistics = &self.statistics[graph_id];
+                let best_known = |problem: Problem| {
+                    self.best_known
+                        .as_ref()
+                        .and_then(|registry| registry.get(graph_id, problem))
+                };
+                let mut statistics = ReportRunStatistics::new(
+                    &graph_statistics.runs,
+                    target.filter(|_| problem.is_some()),
+                    problem.and_then(best_known),
+                    objective.unwrap_or_default(),
+                );
+                if problem.is_none() {
+                    statistics.fitness = None;
+                }
+                let configurations = graph_statistics
+                    .configurations
                     .iter()
-                    .map(|r| r.fitness)
-                    .fold(f64::NEG_INFINITY, f64::max);
+                    .map(|((configuration, problem), stats)| {
+                        let objective = self.objectives.get(*problem);
+                        ReportConfiguration {
+                            configuration: configuration.clone(),
+                            algorithm: stats.algorithm.clone(),
+                            problem: *problem,
+                            objective,
+                            best_fitness: objective
+                                .best(stats.runs.fitness.values().iter().copied()),
+                            statistics: ReportRunStatistics::new(
+                                &stats.runs,
+                                target,
+                                best_known(*problem),
+                                objective,
+                            ),
+                            convergence: (!stats.traces.is_empty())
+                                .then(|| convergence::average(&stats.traces, DEFAULT_CURVE_POINTS)),
+                        }
+                    })
+                    .collect();
+
+                let mut results = results.clone();
+                if let Some(objective) = objective {
+                    results.sort_by(|a, b| objective.compare(a.fitness, b.fitness));
+                }
 
                 (
                     graph_id.clone(),
                     ReportGraphDetails {
+                        instance: catalog.get(graph_id).cloned(),
                         results_collected: results.len(),
+                        objective,
                         best_fitness,
+                        best_result,
+                        lower_bound,
+                        gap_percent,
+                        target,
+                        new_record: new_records.iter().any(|r| &r.graph_id == graph_id),
+                        statistics,
+                        configurations,
                         avg_processing_time_ms: avg_time_ms,
                         total_processing_time_ms: total_time_ms,
-                        results: results.clone(),
+                        results,
                     },
                 )
             })
@@ -164,6 +455,8 @@ impl ResultAggregator {
             task_summary,
             graphs,
             workers,
+            comparison: comparison::compare(&self.statistics, &self.objectives),
+            new_records: new_records.to_vec(),
         };
 
         let json_data = serde_json::to_string_pretty(&report)?;
@@ -1,19 +1,32 @@
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use std::error::Error;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::Mutex;
+use tokio::time::timeout;
+use uuid::Uuid;
 
 use crate::common::Request;
 use crate::common::Response;
 use crate::host::result_aggregator::ResultAggregator;
+use crate::host::store::{AttemptOutcome, ResultStore, SharedStore};
 use crate::host::task_manager::TaskManager;
+use crate::host::validator::ResultValidator;
+
+/// Quanto tempo o host segura um pedido de task de um worker com avaliadores
+/// esperando surgir um job de avaliação.
+const EVALUATION_IDLE_WAIT: Duration = Duration::from_secs(2);
+/// Quanto tempo o host segura a consulta de um lote ainda incompleto.
+const BATCH_POLL_WAIT: Duration = Duration::from_secs(1);
 
 pub async fn start_server(
     addr: &str,
     task_manager: Arc<Mutex<TaskManager>>,
     result_aggregator: Arc<Mutex<ResultAggregator>>,
+    validator: Option<Arc<dyn ResultValidator>>,
+    store: Option<SharedStore>,
 ) -> Result<(), Box<dyn Error>> {
     let listener = TcpListener::bind(addr).await?;
     info!("Host escutando em {addr}");
@@ -21,12 +34,25 @@ pub async fn start_server(
     loop {
         let (socket, remote_addr) = listener.accept().await?;
         info!("Worker {remote_addr}, se conectando");
+        // Mensagens pequenas e respostas curtas: sem Nagle, cada resposta sai na hora.
+        if let Err(e) = socket.set_nodelay(true) {
+            warn!("Falha ao desativar o algoritmo de Nagle para {remote_addr}: {e}");
+        }
 
         let task_manager_clone = Arc::clone(&task_manager);
         let result_aggregator_clone = Arc::clone(&result_aggregator);
+        let validator_clone = validator.clone();
+        let store_clone = store.clone();
 
         tokio::spawn(async move {
-            if let Err(e) = handle_client(socket, task_manager_clone, result_aggregator_clone).await
+            if let Err(e) = handle_client(
+                socket,
+                task_manager_clone,
+                result_aggregator_clone,
+                validator_clone,
+                store_clone,
+            )
+            .await
             {
                 error!("Error {remote_addr}: {e}");
             }
@@ -34,10 +60,46 @@ pub async fn start_server(
     }
 }
 
+/// Atende a conexão e, quando ela termina, esquece os avaliadores registrados por ela
+/// e devolve à fila os jobs de avaliação que o worker recebeu e não chegou a devolver.
 async fn handle_client(
     socket: TcpStream,
     task_manager: Arc<Mutex<TaskManager>>,
     result_aggregator: Arc<Mutex<ResultAggregator>>,
+    validator: Option<Arc<dyn ResultValidator>>,
+    store: Option<SharedStore>,
+) -> Result<(), Box<dyn Error>> {
+    let mut evaluator = None;
+    let outcome = serve_client(
+        socket,
+        &task_manager,
+        result_aggregator,
+        validator,
+        store,
+        &mut evaluator,
+    )
+    .await
+    .map_err(|e| e.to_string());
+    if let Some(worker_id) = evaluator {
+        let released = task_manager.lock().await.release_evaluations(worker_id);
+        if released > 0 {
+            warn!(
+                "{released} jobs de avaliação do trabalhador {worker_id} voltaram para a fila após a desconexão"
+            );
+        }
+    }
+    Ok(outcome?)
+}
+
+/// `evaluator` recebe o id do worker assim que ele registra avaliadores ou recebe um
+/// job de avaliação por esta conexão.
+async fn serve_client(
+    socket: TcpStream,
+    task_manager: &Mutex<TaskManager>,
+    result_aggregator: Arc<Mutex<ResultAggregator>>,
+    validator: Option<Arc<dyn ResultValidator>>,
+    store: Option<SharedStore>,
+    evaluator: &mut Option<Uuid>,
 ) -> Result<(), Box<dyn Error>> {
     let mut reader = BufReader::new(socket);
     let mut line_buffer = String::new();
@@ -58,35 +120,185 @@ async fn handle_client(
         debug!(r"Recebida solicitação do trabalhador: {msg:?}");
 
         let response = match msg {
-            Request::RequestTask { worker_id } => {
+            Request::Register {
+                worker_id,
+                algorithms,
+                problems,
+                evaluators,
+            } => {
+                info!(
+                    "Trabalhador {worker_id} suporta os algoritmos {algorithms:?} e os avaliadores {evaluators:?}"
+                );
+                if !evaluators.is_empty() {
+                    *evaluator = Some(worker_id);
+                }
                 let mut tm = task_manager.lock().await;
-                if let Some(task) = tm.get_next_task(worker_id) {
-                    info!(
-                        "Atribuindo tarefa {} para o trabalhador {}",
-                        task.id, worker_id
-                    );
-                    Response::AssignTask { task }
-                } else {
-                    debug!("Nenhuma tarefa disponível para o trabalhador {worker_id}");
-                    Response::NoTaskAvailable
+                tm.register_worker(worker_id, algorithms, &problems, evaluators);
+                Response::Ack
+            }
+            Request::RequestTask { worker_id } => {
+                let response = next_work(task_manager, worker_id).await;
+                match &response {
+                    Response::AssignTask { task } => {
+                        let task = task.clone();
+                        record(store.as_ref(), move |store| {
+                            store.task_assigned(&task, worker_id)
+                        })
+                        .await;
+                    }
+                    Response::AssignEvaluation { .. } => *evaluator = Some(worker_id),
+                    _ => {}
                 }
+                response
             }
             Request::ReportResult { worker_id, result } => {
                 info!(
                     "Recebido resultado para a tarefa {} do trabalhador {}",
                     result.task_id, worker_id
                 );
+                // A validação pode carregar o grafo do disco e percorrer toda a solução;
+                // ela roda fora das threads do runtime para não atrasar as outras conexões.
+                let (result, rejection) = match &validator {
+                    Some(validator) => {
+                        let validator = Arc::clone(validator);
+                        tokio::task::spawn_blocking(move || {
+                            let rejection = validator.validate(&result).err();
+                            (result, rejection)
+                        })
+                        .await?
+                    }
+                    None => (result, None),
+                };
                 let mut tm = task_manager.lock().await;
-                tm.mark_task_completed(result.task_id)?;
+                if let Some(reason) = rejection {
+                    warn!(
+                        "Resultado da tarefa {} do trabalhador {} rejeitado: {}",
+                        result.task_id, worker_id, reason
+                    );
+                    let requeued = tm.mark_task_failed(result.task_id);
+                    drop(tm);
+                    let task_id = result.task_id;
+                    record(store.as_ref(), move |store| {
+                        store.task_failed(
+                            task_id,
+                            worker_id,
+                            AttemptOutcome::Rejected,
+                            &reason,
+                            requeued,
+                        )
+                    })
+                    .await;
+                } else {
+                    let task_id = result.task_id;
+                    let merged = tm.merge_island_result(result);
+                    tm.mark_task_completed(task_id)?;
+                    drop(tm);
+                    let stored = merged.clone();
+                    record(store.as_ref(), move |store| {
+                        store.task_completed(task_id, worker_id, stored.as_ref())
+                    })
+                    .await;
 
-                let mut ra = result_aggregator.lock().await;
-                ra.add_result(result)?;
+                    if let Some(result) = merged {
+                        let mut ra = result_aggregator.lock().await;
+                        ra.add_result(result)?;
+                    }
+                }
+                Response::Ack
+            }
+            Request::ReportFailure {
+                worker_id,
+                task_id,
+                reason,
+            } => {
+                warn!("Trabalhador {worker_id} falhou na tarefa {task_id}: {reason}");
+                let requeued = task_manager.lock().await.mark_task_failed(task_id);
+                record(store.as_ref(), move |store| {
+                    store.task_failed(
+                        task_id,
+                        worker_id,
+                        AttemptOutcome::Failed,
+                        &reason,
+                        requeued,
+                    )
+                })
+                .await;
                 Response::Ack
             }
             Request::Heartbeat { worker_id } => {
                 debug!("Recebido heartbeat do trabalhador {worker_id}");
                 Response::Ack
             }
+            Request::Migrate {
+                worker_id,
+                task_id,
+                migrants,
+            } => {
+                debug!(
+                    "Trabalhador {worker_id} enviou {} emigrantes da tarefa {task_id}",
+                    migrants.len()
+                );
+                let mut tm = task_manager.lock().await;
+                Response::Migrants {
+                    migrants: tm.migrate(task_id, migrants),
+                }
+            }
+            Request::SubmitBatch {
+                worker_id,
+                task_id,
+                evaluator,
+                individuals,
+            } => {
+                let mut tm = task_manager.lock().await;
+                match tm.submit_batch(worker_id, task_id, &evaluator, individuals) {
+                    Ok(batch_id) => Response::BatchAccepted { batch_id },
+                    Err(reason) => {
+                        // Comum quando todos os workers executam tasks; não é um problema.
+                        debug!("Lote da tarefa {task_id} recusado: {reason}");
+                        Response::Rejected { reason }
+                    }
+                }
+            }
+            Request::PollBatch {
+                worker_id: _,
+                batch_id,
+            } => poll_batch(task_manager, batch_id).await,
+            Request::CancelBatch {
+                worker_id,
+                batch_id,
+            } => {
+                if task_manager.lock().await.cancel_batch(batch_id) {
+                    warn!("Trabalhador {worker_id} desistiu do lote {batch_id}");
+                }
+                Response::Ack
+            }
+            Request::ReportEvaluation {
+                worker_id,
+                job_id,
+                fitnesses,
+            } => {
+                let mut tm = task_manager.lock().await;
+                if let Err(e) = tm.complete_evaluation(job_id, fitnesses) {
+                    warn!("Avaliação do trabalhador {worker_id} descartada: {e}");
+                }
+                Response::Ack
+            }
+            Request::ReportEvaluationFailure {
+                worker_id,
+                job_id,
+                reason,
+            } => {
+                warn!("Trabalhador {worker_id} falhou no job de avaliação {job_id}: {reason}");
+                let mut tm = task_manager.lock().await;
+                tm.fail_evaluation(job_id);
+                Response::Ack
+            }
+            Request::Status => {
+                let tm = task_manager.lock().await;
+                Response::Status {
+                    status: tm.status(),
+                }
+            }
         };
 
         let encoded: Vec<u8> = serde_json::to_vec(&response)?;
@@ -96,3 +308,81 @@ async fn handle_client(
         debug!("Resposta enviada para o trabalhador: {response:?}");
     }
 }
+
+/// Grava um evento no armazenamento, se houver um. A gravação é bloqueante (o SQLite
+/// sincroniza com o disco a cada transação) e por isso roda fora das threads do
+/// runtime. Falhas de gravação são apenas registradas no log, para não derrubar a
+/// conexão do worker.
+async fn record(
+    store: Option<&SharedStore>,
+    event: impl FnOnce(&mut dyn ResultStore) -> Result<(), String> + Send + 'static,
+) {
+    if let Some(store) = store {
+        let store = Arc::clone(store);
+        let written = tokio::task::spawn_blocking(move || {
+            let mut store = store.blocking_lock();
+            event(&mut *store)
+        })
+        .await;
+        match written {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => error!("Falha ao gravar no armazenamento de resultados: {e}"),
+            Err(e) => error!("Gravação no armazenamento de resultados interrompida: {e}"),
+        }
+    }
+}
+
+/// Jobs de avaliação têm prioridade sobre as tasks. Workers com avaliadores que
+/// não recebem trabalho esperam até `EVALUATION_IDLE_WAIT` por um job novo.
+async fn next_work(task_manager: &Mutex<TaskManager>, worker_id: Uuid) -> Response {
+    let mut tm = task_manager.lock().await;
+    if let Some(job) = tm.next_evaluation(worker_id) {
+        return Response::AssignEvaluation { job };
+    }
+    if let Some(task) = tm.get_next_task(worker_id) {
+        info!(
+            "Atribuindo tarefa {} para o trabalhador {}",
+            task.id, worker_id
+        );
+        return Response::AssignTask { task };
+    }
+    if tm.accepts_evaluations(worker_id) {
+        let signal = tm.evaluation_signal();
+        let notified = signal.notified();
+        tokio::pin!(notified);
+        // Registra o interesse antes de soltar o lock para não perder a notificação.
+        notified.as_mut().enable();
+        drop(tm);
+        let _ = timeout(EVALUATION_IDLE_WAIT, notified).await;
+        if let Some(job) = task_manager.lock().await.next_evaluation(worker_id) {
+            return Response::AssignEvaluation { job };
+        }
+    }
+    debug!("Nenhuma tarefa disponível para o trabalhador {worker_id}");
+    Response::NoTaskAvailable
+}
+
+async fn poll_batch(task_manager: &Mutex<TaskManager>, batch_id: Uuid) -> Response {
+    let mut tm = task_manager.lock().await;
+    match tm.batch_results(batch_id) {
+        Some(Some(fitnesses)) => {
+            return Response::BatchResults {
+                fitnesses: Some(fitnesses),
+            };
+        }
+        Some(None) => {}
+        None => {
+            return Response::Rejected {
+                reason: format!("Lote {batch_id} desconhecido"),
+            };
+        }
+    }
+    let signal = tm.evaluation_signal();
+    let notified = signal.notified();
+    tokio::pin!(notified);
+    notified.as_mut().enable();
+    drop(tm);
+    let _ = timeout(BATCH_POLL_WAIT, notified).await;
+    let fitnesses = task_manager.lock().await.batch_results(batch_id).flatten();
+    Response::BatchResults { fitnesses }
+}
@@ -0,0 +1,543 @@
+use std::{collections::HashSet, error::Error};
+
+use log::info;
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Row, params, types::Type};
+use uuid::Uuid;
+
+use super::{
+    catalog::InstanceCatalog,
+    store::{AttemptOutcome, ResultStore},
+};
+use crate::common::{Problem, Task, TaskResult, TracePoint};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS experiments (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    definition TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TABLE IF NOT EXISTS instances (
+    graph_id TEXT PRIMARY KEY,
+    vertices INTEGER NOT NULL,
+    edges INTEGER NOT NULL,
+    statistics TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS configurations (
+    id INTEGER PRIMARY KEY,
+    experiment_id INTEGER NOT NULL REFERENCES experiments(id),
+    label TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    problem TEXT NOT NULL,
+    config TEXT NOT NULL,
+    UNIQUE (experiment_id, label, problem)
+);
+CREATE TABLE IF NOT EXISTS tasks (
+    id TEXT PRIMARY KEY,
+    experiment_id INTEGER NOT NULL REFERENCES experiments(id),
+    configuration_id INTEGER NOT NULL REFERENCES configurations(id),
+    graph_id TEXT NOT NULL,
+    run_number INTEGER NOT NULL,
+    seed TEXT NOT NULL,
+    island INTEGER,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS attempts (
+    id INTEGER PRIMARY KEY,
+    task_id TEXT NOT NULL REFERENCES tasks(id),
+    worker_id TEXT NOT NULL,
+    started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at TEXT,
+    outcome TEXT,
+    reason TEXT
+);
+CREATE TABLE IF NOT EXISTS results (
+    task_id TEXT PRIMARY KEY REFERENCES tasks(id),
+    experiment_id INTEGER NOT NULL REFERENCES experiments(id),
+    graph_id TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    configuration TEXT NOT NULL,
+    problem TEXT NOT NULL,
+    seed TEXT NOT NULL,
+    worker_id TEXT NOT NULL,
+    fitness REAL NOT NULL,
+    processing_time_ms INTEGER NOT NULL,
+    interations_run INTEGER NOT NULL,
+    lower_bound REAL,
+    optimal INTEGER NOT NULL,
+    solution_data BLOB NOT NULL,
+    trace TEXT,
+    received_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS tasks_by_experiment ON tasks (experiment_id, status);
+CREATE INDEX IF NOT EXISTS results_by_experiment ON results (experiment_id, graph_id);
+";
+
+/// Execução já concluída de um experimento: grafo, configuração, variante e número
+/// da execução.
+pub type CompletedRun = (String, String, Problem, u32);
+
+/// Banco SQLite com as tasks, tentativas e resultados de um experimento, identificado
+/// pelo nome. Reabrir o banco com o mesmo nome retoma o experimento.
+pub struct SqliteStore {
+    connection: Connection,
+    experiment_id: i64,
+}
+
+fn problem_column(row: &Row<'_>, index: usize) -> rusqlite::Result<Problem> {
+    let name: String = row.get(index)?;
+    serde_json::from_value(serde_json::Value::String(name))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+fn uuid_column(row: &Row<'_>, index: usize) -> rusqlite::Result<Uuid> {
+    let text: String = row.get(index)?;
+    Uuid::parse_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+/// A curva de convergência fica gravada em JSON, ou nula quando não foi registrada.
+fn trace_column(row: &Row<'_>, index: usize) -> rusqlite::Result<Vec<TracePoint>> {
+    let Some(text) = row.get::<_, Option<String>>(index)? else {
+        return Ok(Vec::new());
+    };
+    serde_json::from_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+/// Sementes são `u64` e não cabem em um INTEGER do SQLite; ficam gravadas como texto.
+fn seed_column(row: &Row<'_>, index: usize) -> rusqlite::Result<u64> {
+    let text: String = row.get(index)?;
+    text.parse()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+fn query_results(
+    connection: &Connection,
+    experiment_id: i64,
+) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+    let mut statement = connection.prepare(
+        "SELECT task_id, graph_id, algorithm, configuration, problem, seed, worker_id, fitness,
+                solution_data, interations_run, processing_time_ms, lower_bound, optimal, trace
+         FROM results WHERE experiment_id = ?1 ORDER BY rowid",
+    )?;
+    let results = statement
+        .query_map(params![experiment_id], |row| {
+            Ok(TaskResult {
+                task_id: uuid_column(row, 0)?,
+                graph_id: row.get(1)?,
+                algorithm: row.get(2)?,
+                configuration: row.get(3)?,
+                problem: problem_column(row, 4)?,
+                seed: seed_column(row, 5)?,
+                worker_id: uuid_column(row, 6)?,
+                fitness: row.get(7)?,
+                solution_data: row.get(8)?,
+                interations_run: row.get(9)?,
+                processing_time_ms: row.get::<_, i64>(10)? as u64,
+                lower_bound: row.get(11)?,
+                optimal: row.get(12)?,
+                trace: trace_column(row, 13)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(results)
+}
+
+impl SqliteStore {
+    /// Abre (ou cria) o banco em `path` e o experimento `name`. Se o experimento já
+    /// existir, as tasks que ficaram pendentes ou atribuídas na execução anterior
+    /// são marcadas como abandonadas.
+    pub fn open(path: &str, name: &str, definition: &str) -> Result<Self, Box<dyn Error>> {
+        let connection = Connection::open(path)
+            .map_err(|e| format!("Falha ao abrir o banco de resultados '{path}': {e}"))?;
+        connection.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")?;
+        connection.execute_batch(SCHEMA)?;
+
+        let existing: Option<i64> = connection
+            .query_row(
+                "SELECT id FROM experiments WHERE name = ?1",
+                params![name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let experiment_id = if let Some(id) = existing {
+            connection.execute(
+                "UPDATE experiments SET definition = ?1, started_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                params![definition, id],
+            )?;
+            let abandoned = connection.execute(
+                "UPDATE tasks SET status = 'abandoned' WHERE experiment_id = ?1 AND status IN ('pending', 'assigned', 'failed')",
+                params![id],
+            )?;
+            info!(
+                "Retomando o experimento '{name}' do banco '{path}' ({abandoned} tasks da execução anterior abandonadas)"
+            );
+            id
+        } else {
+            connection.execute(
+                "INSERT INTO experiments (name, definition) VALUES (?1, ?2)",
+                params![name, definition],
+            )?;
+            info!("Experimento '{name}' criado no banco '{path}'");
+            connection.last_insert_rowid()
+        };
+
+        Ok(Self {
+            connection,
+            experiment_id,
+        })
+    }
+
+    /// Resultados já gravados para o experimento, para reconstruir o `ResultAggregator`.
+    pub fn load_results(&self) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+        query_results(&self.connection, self.experiment_id)
+    }
+
+    /// Resultados do experimento `name` em um banco existente, sem alterá-lo.
+    pub fn read_results(path: &str, name: &str) -> Result<Vec<TaskResult>, Box<dyn Error>> {
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("Falha ao abrir o banco de resultados '{path}': {e}"))?;
+        let experiment_id: i64 = connection
+            .query_row(
+                "SELECT id FROM experiments WHERE name = ?1",
+                params![name],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| format!("O banco '{path}' não tem o experimento '{name}'"))?;
+        query_results(&connection, experiment_id)
+    }
+
+    /// Execuções do experimento que já têm resultado e não precisam ser repetidas.
+    pub fn completed_runs(&self) -> Result<HashSet<CompletedRun>, Box<dyn Error>> {
+        let mut statement = self.connection.prepare(
+            "SELECT tasks.graph_id, configurations.label, configurations.problem, tasks.run_number
+             FROM results
+             JOIN tasks ON tasks.id = results.task_id
+             JOIN configurations ON configurations.id = tasks.configuration_id
+             WHERE results.experiment_id = ?1",
+        )?;
+        let runs = statement
+            .query_map(params![self.experiment_id], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    problem_column(row, 2)?,
+                    row.get(3)?,
+                ))
+            })?
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(runs)
+    }
+
+    pub fn save_instances(&mut self, catalog: &InstanceCatalog) -> Result<(), Box<dyn Error>> {
+        let transaction = self.connection.transaction()?;
+        for (graph_id, stats) in catalog.instances() {
+            transaction.execute(
+                "INSERT OR REPLACE INTO instances (graph_id, vertices, edges, statistics) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    graph_id,
+                    stats.vertices as i64,
+                    stats.edges as i64,
+                    serde_json::to_string(stats)?
+                ],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Grava as tasks criadas para esta execução do host e as suas configurações.
+    pub fn save_tasks<'a>(
+        &mut self,
+        tasks: impl IntoIterator<Item = &'a Task>,
+    ) -> Result<(), Box<dyn Error>> {
+        let transaction = self.connection.transaction()?;
+        for task in tasks {
+            let configuration = if task.configuration.is_empty() {
+                &task.algorithm
+            } else {
+                &task.configuration
+            };
+            transaction.execute(
+                "INSERT OR IGNORE INTO configurations (experiment_id, label, algorithm, problem, config)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    self.experiment_id,
+                    configuration,
+                    task.algorithm,
+                    task.problem.to_string(),
+                    task.ag_config.to_string()
+                ],
+            )?;
+            transaction.execute(
+                "INSERT INTO tasks (id, experiment_id, configuration_id, graph_id, run_number, seed, island, status)
+                 SELECT ?1, ?2, id, ?4, ?5, ?6, ?7, 'pending'
+                 FROM configurations WHERE experiment_id = ?2 AND label = ?3 AND problem = ?8",
+                params![
+                    task.id.to_string(),
+                    self.experiment_id,
+                    configuration,
+                    task.graph_id,
+                    task.run_number,
+                    task.seed.to_string(),
+                    task.island.as_ref().map(|island| island.island),
+                    task.problem.to_string()
+                ],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn finish_attempt(
+        transaction: &rusqlite::Transaction<'_>,
+        task_id: &str,
+        worker_id: &str,
+        outcome: AttemptOutcome,
+        reason: Option<&str>,
+        status: &str,
+    ) -> rusqlite::Result<()> {
+        transaction.execute(
+            "UPDATE attempts SET finished_at = CURRENT_TIMESTAMP, outcome = ?3, reason = ?4
+             WHERE id = (SELECT MAX(id) FROM attempts WHERE task_id = ?1 AND worker_id = ?2 AND outcome IS NULL)",
+            params![task_id, worker_id, outcome.as_str(), reason],
+        )?;
+        transaction.execute(
+            "UPDATE tasks SET status = ?2 WHERE id = ?1",
+            params![task_id, status],
+        )?;
+        Ok(())
+    }
+}
+
+impl ResultStore for SqliteStore {
+    fn task_assigned(&mut self, task: &Task, worker_id: Uuid) -> Result<(), String> {
+        let transaction = self.connection.transaction().map_err(|e| e.to_string())?;
+        transaction
+            .execute(
+                "INSERT INTO attempts (task_id, worker_id) VALUES (?1, ?2)",
+                params![task.id.to_string(), worker_id.to_string()],
+            )
+            .and_then(|_| {
+                transaction.execute(
+                    "UPDATE tasks SET status = 'assigned' WHERE id = ?1",
+                    params![task.id.to_string()],
+                )
+            })
+            .map_err(|e| e.to_string())?;
+        transaction.commit().map_err(|e| e.to_string())
+    }
+
+    fn task_completed(
+        &mut self,
+        task_id: Uuid,
+        worker_id: Uuid,
+        result: Option<&TaskResult>,
+    ) -> Result<(), String> {
+        let transaction = self.connection.transaction().map_err(|e| e.to_string())?;
+        Self::finish_attempt(
+            &transaction,
+            &task_id.to_string(),
+            &worker_id.to_string(),
+            AttemptOutcome::Completed,
+            None,
+            "completed",
+        )
+        .map_err(|e| e.to_string())?;
+        if let Some(result) = result {
+            transaction
+                .execute(
+                    "INSERT OR REPLACE INTO results (task_id, experiment_id, graph_id, algorithm, configuration,
+                         problem, seed, worker_id, fitness, processing_time_ms, interations_run, lower_bound,
+                         optimal, solution_data, trace)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                    params![
+                        result.task_id.to_string(),
+                        self.experiment_id,
+                        result.graph_id,
+                        result.algorithm,
+                        result.configuration(),
+                        result.problem.to_string(),
+                        result.seed.to_string(),
+                        result.worker_id.to_string(),
+                        result.fitness,
+                        result.processing_time_ms as i64,
+                        result.interations_run,
+                        result.lower_bound,
+                        result.optimal,
+                        result.solution_data,
+                        (!result.trace.is_empty())
+                            .then(|| serde_json::to_string(&result.trace))
+                            .transpose()
+                            .map_err(|e| e.to_string())?
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+        }
+        transaction.commit().map_err(|e| e.to_string())
+    }
+
+    fn task_failed(
+        &mut self,
+        task_id: Uuid,
+        worker_id: Uuid,
+        outcome: AttemptOutcome,
+        reason: &str,
+        requeued: bool,
+    ) -> Result<(), String> {
+        let transaction = self.connection.transaction().map_err(|e| e.to_string())?;
+        Self::finish_attempt(
+            &transaction,
+            &task_id.to_string(),
+            &worker_id.to_string(),
+            outcome,
+            Some(reason),
+            if requeued { "pending" } else { "failed" },
+        )
+        .map_err(|e| e.to_string())?;
+        transaction.commit().map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{GAConfig, IslandModel, IslandTask};
+
+    fn task(run_number: u32) -> Task {
+        Task::new(
+            "g.txt".to_string(),
+            run_number,
+            "ga",
+            Problem::Roman,
+            &GAConfig::default(),
+        )
+        .unwrap()
+    }
+
+    fn result(task: &Task, worker_id: Uuid, fitness: f64) -> TaskResult {
+        TaskResult {
+            task_id: task.id,
+            graph_id: task.graph_id.clone(),
+            algorithm: task.algorithm.clone(),
+            configuration: task.configuration.clone(),
+            problem: task.problem,
+            seed: task.seed,
+            worker_id,
+            fitness,
+            solution_data: vec![2, 0, 0],
+            interations_run: 10,
+            processing_time_ms: 5,
+            lower_bound: Some(2.0),
+            optimal: false,
+            trace: vec![TracePoint {
+                elapsed_ms: 1.0,
+                iteration: 1,
+                best_fitness: fitness,
+            }],
+        }
+    }
+
+    /// Banco temporário, apagado (com os arquivos do WAL) ao fim do teste.
+    struct TempDatabase(String);
+
+    impl TempDatabase {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("kambo-hive-{}.sqlite", Uuid::new_v4()));
+            Self(path.to_string_lossy().into_owned())
+        }
+    }
+
+    impl Drop for TempDatabase {
+        fn drop(&mut self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let _ = std::fs::remove_file(format!("{}{suffix}", self.0));
+            }
+        }
+    }
+
+    #[test]
+    fn reopened_experiment_keeps_completed_runs_and_results() {
+        let database = TempDatabase::new();
+        let worker_id = Uuid::new_v4();
+        let completed = task(0);
+        let pending = task(1);
+        // Um grupo de duas ilhas da execução 2: o resultado agregado é gravado só
+        // sob o id da ilha que terminou por último.
+        let group_id = Uuid::new_v4();
+        let islands: Vec<Task> = (0..2)
+            .map(|island| {
+                let mut task = task(2);
+                task.island = Some(IslandTask {
+                    group_id,
+                    island,
+                    model: IslandModel {
+                        islands: 2,
+                        topology: Default::default(),
+                        migration_interval: 5,
+                        migrants: 1,
+                    },
+                });
+                task
+            })
+            .collect();
+        let island_result = result(&islands[1], worker_id, 3.0);
+
+        {
+            let mut store = SqliteStore::open(&database.0, "exp", "{}").unwrap();
+            store
+                .save_tasks([&completed, &pending].into_iter().chain(&islands))
+                .unwrap();
+            for task in [&completed, &pending].into_iter().chain(&islands) {
+                store.task_assigned(task, worker_id).unwrap();
+            }
+            store
+                .task_completed(
+                    completed.id,
+                    worker_id,
+                    Some(&result(&completed, worker_id, 2.0)),
+                )
+                .unwrap();
+            store
+                .task_completed(islands[0].id, worker_id, None)
+                .unwrap();
+            store
+                .task_completed(islands[1].id, worker_id, Some(&island_result))
+                .unwrap();
+        }
+
+        let store = SqliteStore::open(&database.0, "exp", "{}").unwrap();
+        let runs = store.completed_runs().unwrap();
+        let expected: HashSet<CompletedRun> = [0, 2]
+            .into_iter()
+            .map(|run| ("g.txt".to_string(), "ga".to_string(), Problem::Roman, run))
+            .collect();
+        assert_eq!(runs, expected);
+
+        let mut results = store.load_results().unwrap();
+        results.sort_by(|a, b| a.fitness.total_cmp(&b.fitness));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].task_id, completed.id);
+        assert_eq!(results[0].seed, completed.seed);
+        assert_eq!(results[1].task_id, island_result.task_id);
+        for result in &results {
+            assert_eq!(result.worker_id, worker_id);
+            assert_eq!(result.solution_data, vec![2, 0, 0]);
+            assert_eq!(result.lower_bound, Some(2.0));
+            assert_eq!(result.trace.len(), 1);
+        }
+
+        let status: String = store
+            .connection
+            .query_row(
+                "SELECT status FROM tasks WHERE id = ?1",
+                params![pending.id.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(status, "abandoned");
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::common::{Objective, Problem, TaskResult, TracePoint};
+
+/// Estatísticas descritivas de uma amostra.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    /// Desvio padrão amostral; zero com uma única observação.
+    pub std_dev: f64,
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    /// Coeficiente de variação (`std_dev / |mean|`); `None` se a média for zero.
+    pub cv: Option<f64>,
+}
+
+/// Amostra atualizada a cada observação: média e variância pelo método de Welford
+/// e os valores mantidos em ordem para os quantis.
+#[derive(Debug, Clone, Default)]
+pub struct RunningStats {
+    mean: f64,
+    m2: f64,
+    sorted: Vec<f64>,
+}
+
+impl RunningStats {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            mean: 0.0,
+            m2: 0.0,
+            sorted: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        let index = self.sorted.partition_point(|&v| v <= value);
+        self.sorted.insert(index, value);
+        let delta = value - self.mean;
+        self.mean += delta / self.sorted.len() as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    #[must_use]
+    pub const fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// Valores observados, em ordem crescente.
+    #[must_use]
+    pub fn values(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    /// Quantil `q` (entre 0 e 1) por interpolação linear entre as observações ordenadas.
+    #[must_use]
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let last = self.sorted.len().checked_sub(1)?;
+        let position = q.clamp(0.0, 1.0) * last as f64;
+        let lower = position.floor() as usize;
+        let upper = position.ceil() as usize;
+        let fraction = position - lower as f64;
+        Some(self.sorted[lower] + (self.sorted[upper] - self.sorted[lower]) * fraction)
+    }
+
+    /// Quantas observações são iguais ou melhores que `target` segundo `objective`.
+    #[must_use]
+    pub fn count_reaching(&self, target: f64, objective: Objective) -> usize {
+        match objective {
+            Objective::Minimize => self.sorted.partition_point(|&v| v <= target),
+            Objective::Maximize => self.count() - self.sorted.partition_point(|&v| v < target),
+        }
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> Option<Summary> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let std_dev = if count > 1 {
+            (self.m2 / (count - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        Some(Summary {
+            count,
+            mean: self.mean,
+            std_dev,
+            min: self.sorted[0],
+            q1: self.quantile(0.25)?,
+            median: self.quantile(0.5)?,
+            q3: self.quantile(0.75)?,
+            max: self.sorted[count - 1],
+            cv: (self.mean != 0.0).then(|| std_dev / self.mean.abs()),
+        })
+    }
+}
+
+/// Fitness e tempo de processamento de um conjunto de execuções.
+#[derive(Debug, Clone, Default)]
+pub struct RunStatistics {
+    pub fitness: RunningStats,
+    pub processing_time_ms: RunningStats,
+    /// Fitness e tempo de cada execução, na ordem de chegada.
+    runs: Vec<(f64, f64)>,
+}
+
+impl RunStatistics {
+    pub fn push(&mut self, result: &TaskResult) {
+        let time_ms = result.processing_time_ms as f64;
+        self.fitness.push(result.fitness);
+        self.processing_time_ms.push(time_ms);
+        self.runs.push((result.fitness, time_ms));
+    }
+
+    /// Tempos de processamento das execuções que atingiram `target`.
+    #[must_use]
+    pub fn time_to_target(&self, target: f64, objective: Objective) -> RunningStats {
+        let mut times = RunningStats::new();
+        for &(fitness, time_ms) in &self.runs {
+            if !objective.is_better(target, fitness) {
+                times.push(time_ms);
+            }
+        }
+        times
+    }
+
+    /// Fração das execuções que atingiram `target`.
+    #[must_use]
+    pub fn success_rate(&self, target: f64, objective: Objective) -> Option<f64> {
+        let count = self.fitness.count();
+        (count > 0).then(|| self.fitness.count_reaching(target, objective) as f64 / count as f64)
+    }
+}
+
+/// Configuração comparada no experimento: rótulo e variante do problema.
+pub type ConfigurationKey = (String, Problem);
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigurationStatistics {
+    pub algorithm: String,
+    pub runs: RunStatistics,
+    /// Curvas de convergência das execuções que as registraram.
+    pub traces: Vec<Vec<TracePoint>>,
+}
+
+/// Estatísticas de um grafo, no total e por configuração.
+#[derive(Debug, Clone, Default)]
+pub struct GraphStatistics {
+    pub runs: RunStatistics,
+    pub configurations: BTreeMap<ConfigurationKey, ConfigurationStatistics>,
+}
+
+impl GraphStatistics {
+    pub fn push(&mut self, result: &TaskResult) {
+        self.runs.push(result);
+        let configuration = self
+            .configurations
+            .entry((result.configuration().to_string(), result.problem))
+            .or_insert_with(|| ConfigurationStatistics {
+                algorithm: result.algorithm.clone(),
+                runs: RunStatistics::default(),
+                traces: Vec::new(),
+            });
+        configuration.runs.push(result);
+        if !result.trace.is_empty() {
+            configuration.traces.push(result.trace.clone());
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::common::{Task, TaskResult};
+
+/// Como terminou uma tentativa de executar uma task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    Completed,
+    /// O worker informou uma falha.
+    Failed,
+    /// O `ResultValidator` recusou o resultado.
+    Rejected,
+}
+
+impl AttemptOutcome {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// Persistência das tasks e dos resultados, alimentada pelo servidor à medida que
+/// os workers pedem tasks e devolvem resultados.
+pub trait ResultStore: Send + 'static {
+    fn task_assigned(&mut self, task: &Task, worker_id: Uuid) -> Result<(), String>;
+
+    /// `result` é o resultado agregado, ausente para as ilhas de um grupo que ainda
+    /// não terminou.
+    fn task_completed(
+        &mut self,
+        task_id: Uuid,
+        worker_id: Uuid,
+        result: Option<&TaskResult>,
+    ) -> Result<(), String>;
+
+    /// `requeued` indica se a task voltou para a fila ou esgotou as tentativas.
+    fn task_failed(
+        &mut self,
+        task_id: Uuid,
+        worker_id: Uuid,
+        outcome: AttemptOutcome,
+        reason: &str,
+        requeued: bool,
+    ) -> Result<(), String>;
+}
+
+/// Armazenamento compartilhado entre as conexões dos workers.
+pub type SharedStore = Arc<Mutex<dyn ResultStore>>;
@@ -0,0 +1,582 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    comparison::{self, Friedman, SIGNIFICANCE_LEVEL},
+    statistics::{GraphStatistics, RunStatistics},
+};
+use crate::common::{Objective, Objectives, Problem};
+
+/// Formato de uma tabela para artigos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableFormat {
+    /// Ambiente `table` com `tabular` no estilo do pacote booktabs.
+    Latex,
+    Markdown,
+}
+
+impl TableFormat {
+    /// LaTeX para `.tex`; qualquer outra extensão é Markdown.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("tex") => Self::Latex,
+            _ => Self::Markdown,
+        }
+    }
+}
+
+/// Coluna mostrada para cada configuração.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableColumn {
+    /// Melhor fitness das execuções.
+    Best,
+    /// Fitness média.
+    Mean,
+    /// Desvio padrão da fitness.
+    StdDev,
+    /// Tempo médio de processamento, em segundos.
+    Time,
+}
+
+impl TableColumn {
+    const fn header(self) -> &'static str {
+        match self {
+            Self::Best => "Best",
+            Self::Mean => "Mean",
+            Self::StdDev => "Std",
+            Self::Time => "Time (s)",
+        }
+    }
+
+    /// Colunas em que o melhor valor da linha fica em negrito.
+    const fn highlights_best(self) -> bool {
+        matches!(self, Self::Best | Self::Mean)
+    }
+}
+
+fn default_columns() -> Vec<TableColumn> {
+    vec![
+        TableColumn::Best,
+        TableColumn::Mean,
+        TableColumn::StdDev,
+        TableColumn::Time,
+    ]
+}
+
+const fn default_precision() -> usize {
+    2
+}
+
+/// Tabela declarada no experimento: uma linha por instância e, para cada
+/// configuração, as colunas escolhidas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TableSpec {
+    pub path: String,
+    /// Padrão: o formato indicado pela extensão de `path`.
+    #[serde(default)]
+    pub format: Option<TableFormat>,
+    /// Variante comparada; padrão: uma tabela para cada variante com resultados.
+    #[serde(default)]
+    pub problem: Option<Problem>,
+    /// Configurações mostradas, na ordem das colunas; padrão: todas, em ordem alfabética.
+    #[serde(default)]
+    pub configurations: Vec<String>,
+    #[serde(default = "default_columns")]
+    pub columns: Vec<TableColumn>,
+    /// Casas decimais dos valores.
+    #[serde(default = "default_precision")]
+    pub precision: usize,
+    #[serde(default)]
+    pub caption: Option<String>,
+    /// Rótulo do ambiente `table` no LaTeX.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl TableSpec {
+    #[must_use]
+    pub fn format(&self) -> TableFormat {
+        self.format
+            .unwrap_or_else(|| TableFormat::from_path(Path::new(&self.path)))
+    }
+}
+
+/// Valores de uma configuração em uma instância.
+struct Cell {
+    values: Vec<Option<f64>>,
+    bold: Vec<bool>,
+    /// As execuções diferem significativamente das da configuração com a melhor
+    /// média na instância.
+    significant: bool,
+}
+
+struct Table {
+    problem: Problem,
+    objective: Objective,
+    configurations: Vec<String>,
+    rows: Vec<(String, Vec<Option<Cell>>)>,
+    /// Postos médios de Friedman, na ordem de `configurations`.
+    average_ranks: Vec<f64>,
+    friedman: Option<Friedman>,
+    /// Instâncias usadas nos postos médios.
+    ranked_graphs: usize,
+}
+
+fn column_value(column: TableColumn, runs: &RunStatistics, objective: Objective) -> Option<f64> {
+    match column {
+        TableColumn::Best => objective.best(runs.fitness.values().iter().copied()),
+        TableColumn::Mean => runs.fitness.summary().map(|summary| summary.mean),
+        TableColumn::StdDev => runs.fitness.summary().map(|summary| summary.std_dev),
+        TableColumn::Time => runs
+            .processing_time_ms
+            .summary()
+            .map(|summary| summary.mean / 1000.0),
+    }
+}
+
+/// Arredonda para a precisão da tabela, para que valores impressos iguais empatem.
+fn rounded(value: f64, precision: usize) -> f64 {
+    let scale = 10f64.powi(precision as i32);
+    (value * scale).round() / scale
+}
+
+fn build(
+    spec: &TableSpec,
+    statistics: &HashMap<String, GraphStatistics>,
+    objectives: &Objectives,
+    problem: Problem,
+) -> Option<Table> {
+    let objective = objectives.get(problem);
+    let configurations: Vec<String> = if spec.configurations.is_empty() {
+        statistics
+            .values()
+            .flat_map(|graph| graph.configurations.keys())
+            .filter(|(_, p)| *p == problem)
+            .map(|(configuration, _)| configuration.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    } else {
+        spec.configurations.clone()
+    };
+    if configurations.is_empty() {
+        return None;
+    }
+
+    let mut graph_ids: Vec<&String> = statistics
+        .iter()
+        .filter(|(_, graph)| {
+            configurations.iter().any(|configuration| {
+                graph
+                    .configurations
+                    .contains_key(&(configuration.clone(), problem))
+            })
+        })
+        .map(|(graph_id, _)| graph_id)
+        .collect();
+    graph_ids.sort();
+
+    let rows = graph_ids
+        .into_iter()
+        .map(|graph_id| {
+            let graph = &statistics[graph_id];
+            let runs: Vec<Option<&RunStatistics>> = configurations
+                .iter()
+                .map(|configuration| {
+                    graph
+                        .configurations
+                        .get(&(configuration.clone(), problem))
+                        .map(|c| &c.runs)
+                })
+                .collect();
+            (graph_id.clone(), build_row(spec, &runs, objective))
+        })
+        .collect();
+
+    let names: Vec<&str> = configurations.iter().map(String::as_str).collect();
+    let ranking = (names.len() >= 2)
+        .then(|| comparison::compare_problem(statistics, problem, objective, &names));
+    Some(Table {
+        problem,
+        objective,
+        average_ranks: ranking.as_ref().map_or_else(Vec::new, |ranking| {
+            ranking
+                .average_ranks
+                .iter()
+                .map(|rank| rank.average_rank)
+                .collect()
+        }),
+        ranked_graphs: ranking.as_ref().map_or(0, |ranking| ranking.graphs.len()),
+        friedman: ranking.and_then(|ranking| ranking.friedman),
+        configurations,
+        rows,
+    })
+}
+
+fn build_row(
+    spec: &TableSpec,
+    runs: &[Option<&RunStatistics>],
+    objective: Objective,
+) -> Vec<Option<Cell>> {
+    let values: Vec<Option<Vec<Option<f64>>>> = runs
+        .iter()
+        .map(|runs| {
+            runs.map(|runs| {
+                spec.columns
+                    .iter()
+                    .map(|&column| column_value(column, runs, objective))
+                    .collect()
+            })
+        })
+        .collect();
+    let best_per_column: Vec<Option<f64>> = spec
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            if !column.highlights_best() {
+                return None;
+            }
+            objective.best(
+                values
+                    .iter()
+                    .flatten()
+                    .filter_map(|cell| cell[index])
+                    .map(|value| rounded(value, spec.precision)),
+            )
+        })
+        .collect();
+
+    // Referência dos testes: a configuração com a melhor fitness média na instância.
+    let reference = runs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, runs)| {
+            runs.and_then(|runs| runs.fitness.summary())
+                .map(|summary| (index, summary.mean))
+        })
+        .min_by(|(_, a), (_, b)| objective.compare(*a, *b))
+        .map(|(index, _)| index);
+
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(index, values)| {
+            let values = values?;
+            let bold = values
+                .iter()
+                .zip(&best_per_column)
+                .map(|(value, best)| {
+                    value
+                        .zip(*best)
+                        .is_some_and(|(value, best)| rounded(value, spec.precision) == best)
+                })
+                .collect();
+            let significant = reference.filter(|&r| r != index).is_some_and(|r| {
+                runs[r].zip(runs[index]).is_some_and(|(reference, runs)| {
+                    comparison::mann_whitney(reference.fitness.values(), runs.fitness.values())
+                        .is_some_and(|test| test.p_value < SIGNIFICANCE_LEVEL)
+                })
+            });
+            Some(Cell {
+                values,
+                bold,
+                significant,
+            })
+        })
+        .collect()
+}
+
+/// Coluna que recebe o marcador de significância: a média, se estiver na tabela.
+fn marker_column(columns: &[TableColumn]) -> usize {
+    columns
+        .iter()
+        .position(|&column| column == TableColumn::Mean)
+        .unwrap_or(0)
+}
+
+fn latex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn render_latex(spec: &TableSpec, table: &Table, single: bool, lines: &mut Vec<String>) {
+    let width = spec.columns.len();
+    let marker = marker_column(&spec.columns);
+    lines.push("\\begin{table}[htbp]".to_string());
+    lines.push("\\centering".to_string());
+    if let Some(caption) = &spec.caption {
+        let caption = if single {
+            latex_escape(caption)
+        } else {
+            format!(
+                "{} ({})",
+                latex_escape(caption),
+                latex_escape(&table.problem.to_string())
+            )
+        };
+        lines.push(format!("\\caption{{{caption}}}"));
+    }
+    if let Some(label) = &spec.label {
+        if single {
+            lines.push(format!("\\label{{{label}}}"));
+        } else {
+            lines.push(format!("\\label{{{label}:{}}}", table.problem));
+        }
+    }
+    lines.push(format!(
+        "\\begin{{tabular}}{{l{}}}",
+        "r".repeat(width * table.configurations.len())
+    ));
+    lines.push("\\toprule".to_string());
+
+    let groups: Vec<String> = table
+        .configurations
+        .iter()
+        .map(|configuration| {
+            format!(
+                "\\multicolumn{{{width}}}{{c}}{{{}}}",
+                latex_escape(configuration)
+            )
+        })
+        .collect();
+    lines.push(format!(" & {} \\\\", groups.join(" & ")));
+    let rules: Vec<String> = (0..table.configurations.len())
+        .map(|index| {
+            let first = 2 + index * width;
+            format!("\\cmidrule(lr){{{first}-{}}}", first + width - 1)
+        })
+        .collect();
+    lines.push(rules.join(" "));
+    let headers: Vec<&str> = spec.columns.iter().map(|column| column.header()).collect();
+    lines.push(format!(
+        "Instance & {} \\\\",
+        vec![headers.join(" & "); table.configurations.len()].join(" & ")
+    ));
+    lines.push("\\midrule".to_string());
+
+    for (graph_id, cells) in &table.rows {
+        let cells: Vec<String> = cells
+            .iter()
+            .map(|cell| match cell {
+                Some(cell) => cell
+                    .values
+                    .iter()
+                    .zip(&cell.bold)
+                    .enumerate()
+                    .map(|(index, (value, &bold))| {
+                        let Some(value) = value else {
+                            return "--".to_string();
+                        };
+                        let mut text = format!("{value:.*}", spec.precision);
+                        if bold {
+                            text = format!("\\textbf{{{text}}}");
+                        }
+                        if cell.significant && index == marker {
+                            text.push_str("$^{\\dagger}$");
+                        }
+                        text
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" & "),
+                None => vec!["--"; width].join(" & "),
+            })
+            .collect();
+        lines.push(format!(
+            "{} & {} \\\\",
+            latex_escape(graph_id),
+            cells.join(" & ")
+        ));
+    }
+
+    if !table.average_ranks.is_empty() {
+        lines.push("\\midrule".to_string());
+        let ranks: Vec<String> = table
+            .average_ranks
+            .iter()
+            .map(|rank| format!("\\multicolumn{{{width}}}{{c}}{{{rank:.*}}}", spec.precision))
+            .collect();
+        lines.push(format!("Avg. rank & {} \\\\", ranks.join(" & ")));
+    }
+    lines.push("\\bottomrule".to_string());
+    lines.push("\\end{tabular}".to_string());
+
+    let mut notes = vec![format!(
+        "$^{{\\dagger}}$ Significantly different from the best mean on the instance (Mann-Whitney U, $\\alpha = {SIGNIFICANCE_LEVEL}$); {} is {}.",
+        latex_escape(&table.problem.to_string()),
+        objective_name(table.objective)
+    )];
+    if let Some(friedman) = &table.friedman {
+        notes.push(format!(
+            "Friedman test over {} instances: $\\chi^2_F = {:.3}$, $p = {:.4}$.",
+            table.ranked_graphs, friedman.statistic, friedman.p_value
+        ));
+    }
+    lines.push(format!(
+        "\\par\\smallskip{{\\footnotesize {}}}",
+        notes.join(" ")
+    ));
+    lines.push("\\end{table}".to_string());
+}
+
+fn render_markdown(spec: &TableSpec, table: &Table, single: bool, lines: &mut Vec<String>) {
+    let width = spec.columns.len();
+    let marker = marker_column(&spec.columns);
+    match (&spec.caption, single) {
+        (Some(caption), true) => lines.push(format!("**{caption}**")),
+        (Some(caption), false) => lines.push(format!("**{caption} ({})**", table.problem)),
+        (None, false) => lines.push(format!("**{}**", table.problem)),
+        (None, true) => {}
+    }
+    if lines.last().is_some_and(|line| !line.is_empty()) {
+        lines.push(String::new());
+    }
+
+    let mut headers = vec!["Instance".to_string()];
+    for configuration in &table.configurations {
+        for column in &spec.columns {
+            headers.push(format!(
+                "{} {}",
+                markdown_escape(configuration),
+                column.header()
+            ));
+        }
+    }
+    lines.push(format!("| {} |", headers.join(" | ")));
+    let alignment = vec!["---:"; width * table.configurations.len()];
+    lines.push(format!("| --- | {} |", alignment.join(" | ")));
+
+    for (graph_id, cells) in &table.rows {
+        let mut row = vec![markdown_escape(graph_id)];
+        for cell in cells {
+            match cell {
+                Some(cell) => {
+                    for (index, (value, &bold)) in cell.values.iter().zip(&cell.bold).enumerate() {
+                        let Some(value) = value else {
+                            row.push("–".to_string());
+                            continue;
+                        };
+                        let mut text = format!("{value:.*}", spec.precision);
+                        if bold {
+                            text = format!("**{text}**");
+                        }
+                        if cell.significant && index == marker {
+                            text.push_str(" †");
+                        }
+                        row.push(text);
+                    }
+                }
+                None => row.extend(std::iter::repeat_n("–".to_string(), width)),
+            }
+        }
+        lines.push(format!("| {} |", row.join(" | ")));
+    }
+
+    if !table.average_ranks.is_empty() {
+        let mut row = vec!["Avg. rank".to_string()];
+        for rank in &table.average_ranks {
+            row.push(format!("{rank:.*}", spec.precision));
+            row.extend(std::iter::repeat_n(String::new(), width - 1));
+        }
+        lines.push(format!("| {} |", row.join(" | ")));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "† Significantly different from the best mean on the instance (Mann-Whitney U, α = {SIGNIFICANCE_LEVEL}); {} is {}.",
+        table.problem,
+        objective_name(table.objective)
+    ));
+    if let Some(friedman) = &table.friedman {
+        lines.push(String::new());
+        lines.push(format!(
+            "Friedman test over {} instances: χ²F = {:.3}, p = {:.4}.",
+            table.ranked_graphs, friedman.statistic, friedman.p_value
+        ));
+    }
+}
+
+const fn objective_name(objective: Objective) -> &'static str {
+    match objective {
+        Objective::Minimize => "minimized",
+        Objective::Maximize => "maximized",
+    }
+}
+
+/// Gera a tabela descrita por `spec` a partir das estatísticas do `ResultAggregator`.
+#[must_use]
+pub fn render(
+    spec: &TableSpec,
+    statistics: &HashMap<String, GraphStatistics>,
+    objectives: &Objectives,
+) -> String {
+    let problems: Vec<Problem> = match spec.problem {
+        Some(problem) => vec![problem],
+        None => statistics
+            .values()
+            .flat_map(|graph| graph.configurations.keys().map(|(_, problem)| *problem))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+    };
+    let single = spec.problem.is_some() || problems.len() == 1;
+    let format = spec.format();
+
+    let mut lines = Vec::new();
+    if format == TableFormat::Latex {
+        lines.push("% Requer \\usepackage{booktabs}".to_string());
+    }
+    for problem in problems {
+        let Some(table) = build(spec, statistics, objectives, problem) else {
+            continue;
+        };
+        if lines.last().is_some_and(|line| !line.is_empty()) {
+            lines.push(String::new());
+        }
+        match format {
+            TableFormat::Latex => render_latex(spec, &table, single, &mut lines),
+            TableFormat::Markdown => render_markdown(spec, &table, single, &mut lines),
+        }
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Gera a tabela e a grava em `spec.path`.
+pub fn write(
+    spec: &TableSpec,
+    statistics: &HashMap<String, GraphStatistics>,
+    objectives: &Objectives,
+) -> Result<(), Box<dyn Error>> {
+    fs::write(&spec.path, render(spec, statistics, objectives))
+        .map_err(|e| format!("Falha ao gravar a tabela '{}': {e}", spec.path))?;
+    info!("Tabela salva em '{}'", spec.path);
+    Ok(())
+}
@@ -1,34 +1,61 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     error::Error,
+    sync::Arc,
 };
 
 use log::{debug, error, info, warn};
 use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
 use uuid::Uuid;
 
-use crate::common::Task;
+use super::{catalog::InstanceCatalog, evaluation::EvaluationQueue, island::IslandGroup};
+use crate::common::{
+    EvaluationJob, GraphFormat, HostStatus, InstanceStats, IslandModel, IslandTask, Migrant,
+    Objectives, Problem, SyntheticGraph, Task, TaskConfig, TaskResult,
+};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TaskStatus {
     Pending,
     Assigned,
     Completed,
+    /// Falhou em todas as `max_attempts` tentativas e não volta para a fila.
     Failed,
 }
 
+/// Tentativas de cada task quando o experimento não define `max_attempts`.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
 #[derive(Debug, Clone, Copy)]
 pub enum DistributionStrategy {
     Fifo, // First-In, First-Out
     Lifo, // Last-In, First-Out
     Random,
+    LargestFirst,  // Maiores instâncias do catálogo primeiro
+    SmallestFirst, // Menores instâncias do catálogo primeiro
 }
 
 pub struct TaskManager {
     pending_tasks: VecDeque<Task>,
     assigned_tasks: HashMap<Uuid, (Task, Uuid)>, // TaskId -> (Task, WorkerId)
     all_tasks_status: HashMap<Uuid, TaskStatus>,
+    failed_attempts: HashMap<Uuid, u32>, // TaskId -> tentativas que falharam
+    failed_tasks: HashMap<Uuid, Task>,   // Tasks que esgotaram as tentativas
+    max_attempts: u32,
+    worker_algorithms: HashMap<Uuid, HashMap<String, Vec<Problem>>>, // WorkerId -> algoritmos e variantes suportados
     distribution_strategy: DistributionStrategy,
+    catalog: InstanceCatalog,
+    synthetic_graphs: HashMap<String, SyntheticGraph>, // GraphId -> modelo e semente
+    graph_formats: HashMap<String, GraphFormat>,       // GraphId -> formato do arquivo
+    island_groups: HashMap<Uuid, IslandGroup>,         // GroupId -> grupo de ilhas
+    worker_evaluators: HashMap<Uuid, HashSet<String>>, // WorkerId -> avaliadores suportados
+    unservable_warned: HashSet<(String, Problem)>, // Algoritmos e variantes sem worker, já avisados
+    evaluations: EvaluationQueue,
+    evaluation_signal: Arc<Notify>,
+    objectives: Objectives,
 }
 
 impl TaskManager {
@@ -37,37 +64,312 @@ impl TaskManager {
             pending_tasks: VecDeque::new(),
             assigned_tasks: HashMap::new(),
             all_tasks_status: HashMap::new(),
+            failed_attempts: HashMap::new(),
+            failed_tasks: HashMap::new(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            worker_algorithms: HashMap::new(),
             distribution_strategy,
+            catalog: InstanceCatalog::new(),
+            synthetic_graphs: HashMap::new(),
+            graph_formats: HashMap::new(),
+            island_groups: HashMap::new(),
+            worker_evaluators: HashMap::new(),
+            unservable_warned: HashSet::new(),
+            evaluations: EvaluationQueue::new(),
+            evaluation_signal: Arc::new(Notify::new()),
+            objectives: Objectives::default(),
         }
     }
 
-    pub fn add_new_graph_tasks(&mut self, graph_id: &str, num_runs: u32, ag_config: &str) {
-        info!("Adicionando {num_runs} tasks para o graph {graph_id}");
+    /// Define quantas vezes uma task é tentada antes de ser dada como falha.
+    pub fn set_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = max_attempts.max(1);
+    }
+
+    /// Define o sentido de otimização usado para escolher o melhor resultado de um grupo de ilhas.
+    pub fn set_objectives(&mut self, objectives: Objectives) {
+        self.objectives = objectives;
+    }
+
+    pub fn add_new_graph_tasks<C: TaskConfig>(
+        &mut self,
+        graph_id: &str,
+        num_runs: u32,
+        algorithm: &str,
+        configuration: &str,
+        problem: Problem,
+        ag_config: &C,
+    ) -> Result<(), Box<dyn Error>> {
+        info!("Adicionando {num_runs} tasks ({configuration}, {problem}) para o graph {graph_id}");
         for i in 0..num_runs {
-            let task = Task::new(graph_id.to_string(), i, ag_config.to_string());
+            let mut task = Task::new(graph_id.to_string(), i, algorithm, problem, ag_config)?;
+            task.configuration = configuration.to_string();
+            task.synthetic = self.synthetic_graphs.get(graph_id).cloned();
+            task.graph_format = self.graph_formats.get(graph_id).copied();
             self.pending_tasks.push_back(task.clone());
             self.all_tasks_status.insert(task.id, TaskStatus::Pending);
         }
         info!("Tasks pendentes: {}", self.pending_tasks.len());
+        Ok(())
+    }
+
+    /// Adiciona `num_runs` grupos de `model.islands` tasks cooperativas; cada grupo
+    /// conta como uma execução do algoritmo no relatório.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_island_tasks<C: TaskConfig>(
+        &mut self,
+        graph_id: &str,
+        num_runs: u32,
+        algorithm: &str,
+        configuration: &str,
+        problem: Problem,
+        ag_config: &C,
+        model: &IslandModel,
+    ) -> Result<(), Box<dyn Error>> {
+        info!(
+            "Adicionando {num_runs} grupos de {} ilhas ({configuration}, {problem}) para o graph {graph_id}",
+            model.islands
+        );
+        for i in 0..num_runs {
+            let group_id = Uuid::new_v4();
+            for island in 0..model.islands {
+                let mut task = Task::new(graph_id.to_string(), i, algorithm, problem, ag_config)?;
+                task.configuration = configuration.to_string();
+                task.synthetic = self.synthetic_graphs.get(graph_id).cloned();
+                task.graph_format = self.graph_formats.get(graph_id).copied();
+                task.island = Some(IslandTask {
+                    group_id,
+                    island,
+                    model: model.clone(),
+                });
+                self.pending_tasks.push_back(task.clone());
+                self.all_tasks_status.insert(task.id, TaskStatus::Pending);
+            }
+            self.island_groups
+                .insert(group_id, IslandGroup::new(model.clone()));
+        }
+        info!("Tasks pendentes: {}", self.pending_tasks.len());
+        Ok(())
+    }
+
+    /// Roteia os emigrantes da ilha executada por `task_id` e devolve os seus imigrantes.
+    pub fn migrate(&mut self, task_id: Uuid, emigrants: Vec<Migrant>) -> Vec<Migrant> {
+        let Some(island) = self
+            .assigned_tasks
+            .get(&task_id)
+            .and_then(|(task, _)| task.island.as_ref())
+        else {
+            warn!("Migração recebida para a task {task_id}, que não é uma ilha atribuída");
+            return Vec::new();
+        };
+        match self.island_groups.get_mut(&island.group_id) {
+            Some(group) => group.migrate(island.island, emigrants),
+            None => {
+                warn!("Grupo de ilhas {} desconhecido", island.group_id);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Deve ser chamado antes de `mark_task_completed`. Resultados de ilhas ficam
+    /// retidos até o grupo terminar; devolve o resultado a ser agregado, se houver.
+    pub fn merge_island_result(&mut self, result: TaskResult) -> Option<TaskResult> {
+        let island = self
+            .assigned_tasks
+            .get(&result.task_id)
+            .and_then(|(task, _)| task.island.clone());
+        match island {
+            Some(island) => {
+                let objective = self.objectives.get(result.problem);
+                let group = self.island_groups.get_mut(&island.group_id)?;
+                group.complete(island.island, result, objective)
+            }
+            None => Some(result),
+        }
+    }
+
+    pub fn add_instance(&mut self, graph_id: &str, stats: InstanceStats) {
+        self.catalog.insert(graph_id, stats);
+    }
+
+    /// Registra um grafo sintético; as tasks criadas depois para `graph_id` levam o
+    /// modelo e a semente para que o worker gere o grafo em vez de lê-lo do disco.
+    pub fn add_synthetic_graph(&mut self, graph_id: &str, synthetic: SyntheticGraph) {
+        self.synthetic_graphs
+            .insert(graph_id.to_string(), synthetic);
+    }
+
+    /// Fixa o formato do arquivo de `graph_id` nas tasks criadas depois, para o
+    /// worker não precisar detectá-lo.
+    pub fn set_graph_format(&mut self, graph_id: &str, format: GraphFormat) {
+        self.graph_formats.insert(graph_id.to_string(), format);
+    }
+
+    pub const fn catalog(&self) -> &InstanceCatalog {
+        &self.catalog
+    }
+
+    /// Registra os algoritmos do worker, cada um com as variantes de `problems`;
+    /// algoritmos ausentes de `problems` resolvem todas.
+    pub fn register_worker(
+        &mut self,
+        worker_id: Uuid,
+        algorithms: Vec<String>,
+        problems: &BTreeMap<String, Vec<Problem>>,
+        evaluators: Vec<String>,
+    ) {
+        let algorithms = algorithms
+            .into_iter()
+            .map(|algorithm| {
+                let supported = problems
+                    .get(&algorithm)
+                    .cloned()
+                    .unwrap_or_else(|| Problem::ALL.to_vec());
+                (algorithm, supported)
+            })
+            .collect();
+        self.worker_algorithms.insert(worker_id, algorithms);
+        // O novo worker pode resolver combinações já avisadas, ou não: avisa de novo.
+        self.unservable_warned.clear();
+        if evaluators.is_empty() {
+            self.worker_evaluators.remove(&worker_id);
+        } else {
+            self.worker_evaluators
+                .insert(worker_id, evaluators.into_iter().collect());
+        }
+    }
+
+    /// Indica se o worker avalia lotes e, portanto, deve esperar por jobs quando não há tasks.
+    pub fn accepts_evaluations(&self, worker_id: Uuid) -> bool {
+        self.worker_evaluators.contains_key(&worker_id)
+    }
+
+    /// Notificado sempre que um job de avaliação é criado ou concluído.
+    pub fn evaluation_signal(&self) -> Arc<Notify> {
+        Arc::clone(&self.evaluation_signal)
+    }
+
+    /// Divide os indivíduos da task `task_id` entre os outros workers livres que
+    /// registraram `evaluator` e devolve o id do lote. Um worker executando a própria
+    /// task não pede jobs até terminá-la, então não conta; sem nenhum livre, o lote é
+    /// recusado na hora e o coordenador avalia localmente.
+    pub fn submit_batch(
+        &mut self,
+        worker_id: Uuid,
+        task_id: Uuid,
+        evaluator: &str,
+        individuals: Vec<Vec<u8>>,
+    ) -> Result<Uuid, String> {
+        let (task, _) = self
+            .assigned_tasks
+            .get(&task_id)
+            .ok_or_else(|| format!("A task {task_id} não está atribuída"))?;
+        let busy: HashSet<Uuid> = self
+            .assigned_tasks
+            .values()
+            .map(|(_, worker)| *worker)
+            .collect();
+        let workers = self
+            .worker_evaluators
+            .iter()
+            .filter(|(id, evaluators)| {
+                **id != worker_id && !busy.contains(id) && evaluators.contains(evaluator)
+            })
+            .count();
+        if workers == 0 {
+            return Err(format!(
+                "Nenhum outro worker livre registrou o avaliador '{evaluator}'"
+            ));
+        }
+        let batch_id = self
+            .evaluations
+            .submit(task, evaluator, individuals, workers);
+        self.evaluation_signal.notify_waiters();
+        Ok(batch_id)
+    }
+
+    /// Devolve à fila os jobs de avaliação com prazo vencido e acorda quem espera por eles.
+    fn reclaim_evaluations(&mut self) {
+        if self.evaluations.reclaim_expired() > 0 {
+            self.evaluation_signal.notify_waiters();
+        }
+    }
+
+    pub fn next_evaluation(&mut self, worker_id: Uuid) -> Option<EvaluationJob> {
+        self.reclaim_evaluations();
+        let evaluators = self.worker_evaluators.get(&worker_id)?;
+        self.evaluations
+            .next(worker_id, |evaluator| evaluators.contains(evaluator))
+    }
+
+    pub fn complete_evaluation(&mut self, job_id: Uuid, fitnesses: Vec<f64>) -> Result<(), String> {
+        let result = self.evaluations.complete(job_id, fitnesses);
+        self.evaluation_signal.notify_waiters();
+        result
+    }
+
+    pub fn fail_evaluation(&mut self, job_id: Uuid) {
+        self.evaluations.fail(job_id);
+        self.evaluation_signal.notify_waiters();
+    }
+
+    /// Esquece os avaliadores de `worker_id`, que se desconectou, para que novos lotes
+    /// não esperem por ele, e devolve à fila os jobs que ele recebeu. Ao reconectar,
+    /// o worker se registra de novo.
+    pub fn release_evaluations(&mut self, worker_id: Uuid) -> usize {
+        self.worker_evaluators.remove(&worker_id);
+        let released = self.evaluations.release_worker(worker_id);
+        if released > 0 {
+            self.evaluation_signal.notify_waiters();
+        }
+        released
+    }
+
+    /// Descarta um lote que o coordenador abandonou.
+    pub fn cancel_batch(&mut self, batch_id: Uuid) -> bool {
+        self.evaluations.cancel_batch(batch_id)
+    }
+
+    /// `None` se o lote não existe; `Some(None)` enquanto ele não foi totalmente avaliado.
+    pub fn batch_results(&mut self, batch_id: Uuid) -> Option<Option<Vec<f64>>> {
+        self.reclaim_evaluations();
+        self.evaluations.results(batch_id)
+    }
+
+    fn can_run(&self, worker_id: Uuid, task: &Task) -> bool {
+        // Workers que não se registraram aceitam qualquer task.
+        self.worker_algorithms
+            .get(&worker_id)
+            .is_none_or(|algorithms| {
+                algorithms
+                    .get(&task.algorithm)
+                    .is_some_and(|problems| problems.contains(&task.problem))
+            })
     }
 
     pub fn get_next_task(&mut self, worker_id: Uuid) -> Option<Task> {
-        let task = match self.distribution_strategy {
-            DistributionStrategy::Fifo => self.pending_tasks.pop_front(),
-            DistributionStrategy::Lifo => self.pending_tasks.pop_back(),
+        let mut eligible = self
+            .pending_tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| self.can_run(worker_id, task))
+            .map(|(index, _)| index);
+
+        let index = match self.distribution_strategy {
+            DistributionStrategy::Fifo => eligible.next(),
+            DistributionStrategy::Lifo => eligible.next_back(),
             DistributionStrategy::Random => {
-                if self.pending_tasks.is_empty() {
-                    None
-                } else {
-                    let mut rng = rand::rng();
-                    let index = *(0..self.pending_tasks.len())
-                        .collect::<Vec<usize>>()
-                        .choose(&mut rng)
-                        .unwrap();
-                    self.pending_tasks.remove(index)
-                }
+                let mut rng = rand::rng();
+                eligible.collect::<Vec<usize>>().choose(&mut rng).copied()
             }
+            // Empates mantêm a ordem de inserção.
+            DistributionStrategy::LargestFirst => eligible.min_by_key(|&index| {
+                std::cmp::Reverse(self.catalog.size_of(&self.pending_tasks[index].graph_id))
+            }),
+            DistributionStrategy::SmallestFirst => eligible
+                .min_by_key(|&index| self.catalog.size_of(&self.pending_tasks[index].graph_id)),
         };
+        let task = index.and_then(|index| self.pending_tasks.remove(index));
 
         if let Some(task) = task {
             info!("Task {} atribuida ao woerker {}", task.id, worker_id);
@@ -76,14 +378,47 @@ impl TaskManager {
             self.all_tasks_status.insert(task.id, TaskStatus::Assigned);
             Some(task)
         } else {
-            debug!("Não existem tasks pendentes.");
+            debug!("Não existem tasks pendentes que o worker {worker_id} possa executar.");
+            self.warn_unservable();
             None
         }
     }
 
+    /// Avisa, uma vez por algoritmo e variante, sobre tasks pendentes que nenhum
+    /// worker registrado executa; elas ficam na fila até um worker capaz se registrar.
+    fn warn_unservable(&mut self) {
+        if self.worker_algorithms.is_empty() {
+            return;
+        }
+        let mut unservable: BTreeMap<(&str, Problem), usize> = BTreeMap::new();
+        for task in &self.pending_tasks {
+            if !self
+                .worker_algorithms
+                .keys()
+                .any(|&worker_id| self.can_run(worker_id, task))
+            {
+                *unservable
+                    .entry((task.algorithm.as_str(), task.problem))
+                    .or_default() += 1;
+            }
+        }
+        for ((algorithm, problem), count) in unservable {
+            if self
+                .unservable_warned
+                .insert((algorithm.to_string(), problem))
+            {
+                warn!(
+                    "{count} tasks pendentes de '{algorithm}' ({problem}) não podem ser executadas por nenhum dos {} workers registrados; elas ficam na fila até que um worker com esse algoritmo e variante se registre",
+                    self.worker_algorithms.len()
+                );
+            }
+        }
+    }
+
     pub fn mark_task_completed(&mut self, task_id: Uuid) -> Result<(), Box<dyn Error>> {
         if let Some((_, worker_id)) = self.assigned_tasks.remove(&task_id) {
             info!("Task {task_id} finalizada pelo worker {worker_id}");
+            self.evaluations.cancel_task(task_id);
             self.all_tasks_status.insert(task_id, TaskStatus::Completed);
             Ok(())
         } else {
@@ -92,16 +427,81 @@ impl TaskManager {
         }
     }
 
-    pub fn mark_task_failed(&mut self, task_id: Uuid) {
-        if let Some((task, _)) = self.assigned_tasks.remove(&task_id) {
-            error!("Task {task_id} falhou");
-            self.pending_tasks.push_front(task.clone());
+    /// Registra a falha de uma tentativa. A task volta para o fim da fila, para não
+    /// bloquear as demais, até falhar `max_attempts` vezes, quando passa a `Failed`
+    /// e não é mais distribuída. Devolve verdadeiro se a task voltou para a fila.
+    pub fn mark_task_failed(&mut self, task_id: Uuid) -> bool {
+        let Some((task, worker_id)) = self.assigned_tasks.remove(&task_id) else {
+            warn!("Tentando marcar uma task não atribuida: {task_id}");
+            return false;
+        };
+        self.evaluations.cancel_task(task_id);
+        let attempts = self.failed_attempts.entry(task_id).or_insert(0);
+        *attempts += 1;
+        if *attempts >= self.max_attempts {
+            error!(
+                "Task {task_id} falhou no worker {worker_id} pela {attempts}ª vez e não será tentada de novo"
+            );
             self.all_tasks_status.insert(task_id, TaskStatus::Failed);
+            self.failed_tasks.insert(task_id, task);
+            false
         } else {
-            warn!("Tentando marcar uma task não atribuida: {task_id}");
+            warn!(
+                "Task {task_id} falhou no worker {worker_id} (tentativa {attempts} de {}); voltando para o fim da fila",
+                self.max_attempts
+            );
+            self.pending_tasks.push_back(task);
+            self.all_tasks_status.insert(task_id, TaskStatus::Pending);
+            true
         }
     }
 
+    /// Tentativas da task que já falharam.
+    #[must_use]
+    pub fn failed_attempts(&self, task_id: Uuid) -> u32 {
+        self.failed_attempts.get(&task_id).copied().unwrap_or(0)
+    }
+
+    /// Tasks ainda não atribuídas, na ordem da fila.
+    pub fn pending_tasks(&self) -> impl Iterator<Item = &Task> {
+        self.pending_tasks.iter()
+    }
+
+    /// Retira da fila as tasks pendentes para as quais `skip` é verdadeiro e as conta
+    /// como concluídas, por exemplo execuções cujo resultado já foi gravado antes de
+    /// o host reiniciar. Devolve quantas tasks foram retiradas.
+    pub fn skip_tasks(&mut self, mut skip: impl FnMut(&Task) -> bool) -> usize {
+        let before = self.pending_tasks.len();
+        let statuses = &mut self.all_tasks_status;
+        self.pending_tasks.retain(|task| {
+            if skip(task) {
+                statuses.insert(task.id, TaskStatus::Completed);
+                false
+            } else {
+                true
+            }
+        });
+        before - self.pending_tasks.len()
+    }
+
+    /// Tasks sem resultado, com o seu estado e o worker das tasks atribuídas: as
+    /// pendentes, as atribuídas e as que esgotaram as tentativas.
+    pub fn unfinished_tasks(&self) -> impl Iterator<Item = (&Task, TaskStatus, Option<Uuid>)> {
+        let pending = self
+            .pending_tasks
+            .iter()
+            .map(|task| (task, TaskStatus::Pending, None));
+        let assigned = self
+            .assigned_tasks
+            .values()
+            .map(|(task, worker_id)| (task, TaskStatus::Assigned, Some(*worker_id)));
+        let failed = self
+            .failed_tasks
+            .values()
+            .map(|task| (task, TaskStatus::Failed, None));
+        pending.chain(assigned).chain(failed)
+    }
+
     pub fn get_total_tasks(&self) -> usize {
         self.all_tasks_status.len()
     }
@@ -116,4 +516,29 @@ impl TaskManager {
     pub fn get_tasks_status(&self) -> &HashMap<Uuid, TaskStatus> {
         &self.all_tasks_status
     }
+
+    /// Tasks que não serão mais distribuídas: as concluídas e as que falharam em
+    /// todas as tentativas.
+    pub fn get_finished_tasks_count(&self) -> usize {
+        self.count_status(TaskStatus::Completed) + self.count_status(TaskStatus::Failed)
+    }
+
+    fn count_status(&self, status: TaskStatus) -> usize {
+        self.all_tasks_status
+            .values()
+            .filter(|&&s| s == status)
+            .count()
+    }
+
+    pub fn status(&self) -> HostStatus {
+        HostStatus {
+            total_tasks: self.get_total_tasks(),
+            pending: self.count_status(TaskStatus::Pending),
+            assigned: self.count_status(TaskStatus::Assigned),
+            completed: self.count_status(TaskStatus::Completed),
+            failed: self.count_status(TaskStatus::Failed),
+            registered_workers: self.worker_algorithms.len(),
+            instances: self.catalog.instances().clone(),
+        }
+    }
 }
@@ -0,0 +1,8 @@
+use crate::common::TaskResult;
+
+/// Verificação feita pelo host em cada resultado antes de aceitá-lo. Um resultado
+/// rejeitado não chega ao `ResultAggregator` e sua task volta para a fila. O servidor
+/// chama `validate` em uma thread de `spawn_blocking`, então ela pode bloquear.
+pub trait ResultValidator: Send + Sync + 'static {
+    fn validate(&self, result: &TaskResult) -> Result<(), String>;
+}
@@ -52,7 +52,9 @@ pub async fn listen_for_workers(tcp_bind_addr: String) {
         return;
     };
 
-    let socket = match UdpSocket::bind(("0.0.0.0", DISCOVERY_PORT)) {
+    // Socket assíncrono: um `recv_from` bloqueante prenderia uma thread do runtime e
+    // impediria o host de encerrar.
+    let socket = match tokio::net::UdpSocket::bind(("0.0.0.0", DISCOVERY_PORT)).await {
         Ok(s) => s,
         Err(e) => {
             error!("Falha ao escutar na porta de descoberta {DISCOVERY_PORT}: {e}");
@@ -63,22 +65,22 @@ pub async fn listen_for_workers(tcp_bind_addr: String) {
 
     let mut buf = [0; 1024];
     loop {
-        if let Ok((amt, worker_addr)) = socket.recv_from(&mut buf) {
-            if &buf[..amt] == DISCOVERY_MESSAGE {
-                info!("Requisição de descoberta recebida de {worker_addr}");
+        if let Ok((amt, worker_addr)) = socket.recv_from(&mut buf).await
+            && &buf[..amt] == DISCOVERY_MESSAGE
+        {
+            info!("Requisição de descoberta recebida de {worker_addr}");
 
-                if let Some(local_ip) = get_local_ip_for_target(worker_addr) {
-                    let response_addr = format!("{local_ip}:{tcp_port}");
-                    info!("Respondendo para {worker_addr} com o endereço: {response_addr}");
+            if let Some(local_ip) = get_local_ip_for_target(worker_addr) {
+                let response_addr = format!("{local_ip}:{tcp_port}");
+                info!("Respondendo para {worker_addr} com o endereço: {response_addr}");
 
-                    let payload = [RESPONSE_PREFIX, response_addr.as_bytes()].concat();
+                let payload = [RESPONSE_PREFIX, response_addr.as_bytes()].concat();
 
-                    if let Err(e) = socket.send_to(&payload, worker_addr) {
-                        error!("Falha ao enviar resposta para {worker_addr}: {e}");
-                    }
-                } else {
-                    warn!("Não foi possível determinar o IP local para responder a {worker_addr}");
+                if let Err(e) = socket.send_to(&payload, worker_addr).await {
+                    error!("Falha ao enviar resposta para {worker_addr}: {e}");
                 }
+            } else {
+                warn!("Não foi possível determinar o IP local para responder a {worker_addr}");
             }
         }
     }
@@ -1,28 +1,43 @@
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use serde_json;
 use std::error::Error;
+use std::io::{BufRead, BufReader as StdBufReader, Write};
+use std::net::TcpStream as StdTcpStream;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 use tokio::time::sleep;
 use uuid::Uuid;
 
-use crate::common::{GARunner, Request, Response};
+use super::registry::RunnerRegistry;
+use crate::common::{HostChannel, Migrant, Request, Response};
 
-pub async fn start_worker<T: GARunner>(
+/// Tempo máximo de espera por uma resposta do host durante a execução de uma task.
+const HOST_CHANNEL_TIMEOUT: Duration = Duration::from_secs(10);
+/// Tempo máximo de espera pelos fitness de um lote antes de desistir dele.
+const BATCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub async fn start_worker(
     host_addr: &str,
     worker_id: Uuid,
-    ga_runner: Arc<T>,
+    registry: Arc<RunnerRegistry>,
 ) -> Result<(), Box<dyn Error>> {
+    if registry.is_empty() {
+        return Err("Nenhum algoritmo registrado no worker.".into());
+    }
     info!("Trabalhador {worker_id} tentando se conectar ao host em {host_addr}");
 
     loop {
         match TcpStream::connect(host_addr).await {
             Ok(stream) => {
                 info!("Trabalhador {worker_id} conectado ao host.");
+                if let Err(e) = stream.set_nodelay(true) {
+                    warn!("Falha ao desativar o algoritmo de Nagle: {e}");
+                }
                 if let Err(e) =
-                    handle_host_connection(stream, worker_id, Arc::clone(&ga_runner)).await
+                    handle_host_connection(stream, host_addr, worker_id, Arc::clone(&registry))
+                        .await
                 {
                     error!("Conexão com o host perdida ou erro: {e}");
                 }
@@ -37,14 +52,47 @@ pub async fn start_worker<T: GARunner>(
     }
 }
 
-async fn handle_host_connection<T: GARunner>(
+async fn handle_host_connection(
     stream: TcpStream,
+    host_addr: &str,
     worker_id: Uuid,
-    ga_runner: Arc<T>,
+    registry: Arc<RunnerRegistry>,
 ) -> Result<(), Box<dyn Error>> {
     let mut reader = BufReader::new(stream);
     let mut line = String::new();
 
+    let algorithms = registry.algorithms();
+    let problems = registry.problems();
+    let evaluators = registry.evaluators();
+    info!(
+        "Trabalhador {worker_id} anunciando algoritmos: {algorithms:?} e avaliadores: {evaluators:?}"
+    );
+    // O host segura os pedidos de workers com avaliadores até surgir trabalho,
+    // então eles não precisam esperar entre dois pedidos.
+    let idle_wait = if evaluators.is_empty() {
+        Duration::from_secs(2)
+    } else {
+        Duration::ZERO
+    };
+    let register = Request::Register {
+        worker_id,
+        algorithms,
+        problems,
+        evaluators,
+    };
+    let encoded_register = serde_json::to_vec(&register)?;
+    reader.write_all(&encoded_register).await?;
+    reader.write_all(b"\n").await?;
+    reader.flush().await?;
+
+    if reader.read_line(&mut line).await? == 0 {
+        return Err("Host desconectado durante o registro.".into());
+    }
+    match serde_json::from_str(&line)? {
+        Response::Ack => debug!("Trabalhador {worker_id} registrado no host."),
+        other => return Err(format!("Resposta inesperada ao registro: {other:?}").into()),
+    }
+
     loop {
         line.clear();
 
@@ -67,28 +115,61 @@ async fn handle_host_connection<T: GARunner>(
         match response {
             Response::AssignTask { task } => {
                 info!("Trabalhador {} recebeu a tarefa {}", worker_id, task.id);
-                let result = ga_runner.run(task, worker_id);
-                info!(
-                    "Trabalhador {} terminou a tarefa {}. Melhor fitness: {}",
-                    worker_id, result.task_id, result.fitness
-                );
+                let task_id = task.id;
+                let mut channel = TaskHostChannel::new(host_addr, worker_id, task_id);
+                let report_request = match registry.run(task, worker_id, &mut channel) {
+                    Ok(result) => {
+                        info!(
+                            "Trabalhador {} terminou a tarefa {}. Melhor fitness: {}",
+                            worker_id, result.task_id, result.fitness
+                        );
+                        Request::ReportResult { worker_id, result }
+                    }
+                    Err(reason) => {
+                        error!(
+                            "Trabalhador {worker_id} não pode executar a tarefa {task_id}: {reason}"
+                        );
+                        Request::ReportFailure {
+                            worker_id,
+                            task_id,
+                            reason,
+                        }
+                    }
+                };
 
-                let clone_result = result.clone();
-                let report_request = Request::ReportResult { worker_id, result };
-                let encoded_report = serde_json::to_vec(&report_request)?;
-                reader.write_all(&encoded_report).await?;
-                reader.write_all(b"\n").await?;
-                reader.flush().await?;
+                send_report(&mut reader, &report_request).await?;
+                debug!("Trabalhador {worker_id} reportou o resultado da tarefa {task_id}");
+            }
+            Response::AssignEvaluation { job } => {
                 debug!(
-                    "Trabalhador {} reportou o resultado da tarefa {}",
-                    worker_id, clone_result.task_id
+                    "Trabalhador {worker_id} recebeu {} indivíduos do lote {} para avaliar",
+                    job.individuals.len(),
+                    job.batch_id
                 );
+                let job_id = job.id;
+                let report_request = match registry.evaluate(&job) {
+                    Ok(fitnesses) => Request::ReportEvaluation {
+                        worker_id,
+                        job_id,
+                        fitnesses,
+                    },
+                    Err(reason) => {
+                        error!("Trabalhador {worker_id} não pode avaliar o job {job_id}: {reason}");
+                        Request::ReportEvaluationFailure {
+                            worker_id,
+                            job_id,
+                            reason,
+                        }
+                    }
+                };
+
+                send_report(&mut reader, &report_request).await?;
             }
             Response::NoTaskAvailable => {
                 info!(
                     "Trabalhador {worker_id} recebeu NoTaskAvailable. Aguardando novas tarefas..."
                 );
-                sleep(Duration::from_secs(2)).await;
+                sleep(idle_wait).await;
             }
             Response::Ack => {
                 debug!("Trabalhador {worker_id} recebeu Ack.");
@@ -101,6 +182,165 @@ async fn handle_host_connection<T: GARunner>(
                     "Trabalhador {worker_id} recebeu comando: {command_type} com payload {payload}"
                 );
             }
+            Response::Status { .. }
+            | Response::Migrants { .. }
+            | Response::BatchAccepted { .. }
+            | Response::BatchResults { .. }
+            | Response::Rejected { .. } => {
+                warn!("Trabalhador {worker_id} recebeu uma resposta que não pediu: {response:?}");
+            }
+        }
+    }
+}
+
+/// Envia um relatório e espera a confirmação, para que o próximo pedido de task
+/// não fique atrás de uma resposta pendente.
+async fn send_report(
+    reader: &mut BufReader<TcpStream>,
+    report: &Request,
+) -> Result<(), Box<dyn Error>> {
+    let encoded_report = serde_json::to_vec(report)?;
+    reader.write_all(&encoded_report).await?;
+    reader.write_all(b"\n").await?;
+    reader.flush().await?;
+
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Err("Host desconectado.".into());
+    }
+    match serde_json::from_str(&line)? {
+        Response::Ack => Ok(()),
+        other => Err(format!("Resposta inesperada ao relatório: {other:?}").into()),
+    }
+}
+
+/// Canal de uma task em execução com o host.
+///
+/// Os runners são síncronos e ocupam a conexão principal enquanto executam, então
+/// o canal abre uma conexão própria e bloqueante, reaproveitada entre os pedidos
+/// da mesma task.
+struct TaskHostChannel<'a> {
+    host_addr: &'a str,
+    worker_id: Uuid,
+    task_id: Uuid,
+    connection: Option<StdBufReader<StdTcpStream>>,
+}
+
+impl<'a> TaskHostChannel<'a> {
+    const fn new(host_addr: &'a str, worker_id: Uuid, task_id: Uuid) -> Self {
+        Self {
+            host_addr,
+            worker_id,
+            task_id,
+            connection: None,
+        }
+    }
+
+    fn request(&mut self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let connection = match &mut self.connection {
+            Some(connection) => connection,
+            None => {
+                let stream = StdTcpStream::connect(self.host_addr)?;
+                stream.set_read_timeout(Some(HOST_CHANNEL_TIMEOUT))?;
+                stream.set_nodelay(true)?;
+                self.connection.insert(StdBufReader::new(stream))
+            }
+        };
+        let send =
+            |connection: &mut StdBufReader<StdTcpStream>| -> Result<Response, Box<dyn Error>> {
+                let mut encoded = serde_json::to_vec(request)?;
+                encoded.push(b'\n');
+                connection.get_mut().write_all(&encoded)?;
+                let mut line = String::new();
+                if connection.read_line(&mut line)? == 0 {
+                    return Err("Host desconectado.".into());
+                }
+                Ok(serde_json::from_str(&line)?)
+            };
+        let response = send(connection);
+        if response.is_err() {
+            self.connection = None;
+        }
+        response
+    }
+}
+
+impl HostChannel for TaskHostChannel<'_> {
+    fn exchange_migrants(&mut self, emigrants: Vec<Migrant>) -> Result<Vec<Migrant>, String> {
+        let request = Request::Migrate {
+            worker_id: self.worker_id,
+            task_id: self.task_id,
+            migrants: emigrants,
+        };
+        match self.request(&request) {
+            Ok(Response::Migrants { migrants }) => {
+                debug!(
+                    "Task {} recebeu {} imigrantes do host.",
+                    self.task_id,
+                    migrants.len()
+                );
+                Ok(migrants)
+            }
+            Ok(other) => Err(format!("Resposta inesperada à migração: {other:?}")),
+            Err(e) => Err(format!("Falha na migração da task {}: {e}", self.task_id)),
+        }
+    }
+
+    fn evaluate_batch(
+        &mut self,
+        evaluator: &str,
+        individuals: Vec<Vec<u8>>,
+    ) -> Result<Vec<f64>, String> {
+        if individuals.is_empty() {
+            return Ok(Vec::new());
+        }
+        let submit = Request::SubmitBatch {
+            worker_id: self.worker_id,
+            task_id: self.task_id,
+            evaluator: evaluator.to_string(),
+            individuals,
+        };
+        let batch_id = match self.request(&submit) {
+            Ok(Response::BatchAccepted { batch_id }) => batch_id,
+            Ok(Response::Rejected { reason }) => return Err(reason),
+            Ok(other) => return Err(format!("Resposta inesperada ao lote: {other:?}")),
+            Err(e) => {
+                return Err(format!(
+                    "Falha ao enviar o lote da task {}: {e}",
+                    self.task_id
+                ));
+            }
+        };
+
+        let started = Instant::now();
+        let poll = Request::PollBatch {
+            worker_id: self.worker_id,
+            batch_id,
+        };
+        let error = loop {
+            match self.request(&poll) {
+                Ok(Response::BatchResults {
+                    fitnesses: Some(fitnesses),
+                }) => return Ok(fitnesses),
+                Ok(Response::BatchResults { fitnesses: None }) => {}
+                Ok(other) => break format!("Resposta inesperada ao lote: {other:?}"),
+                Err(e) => break format!("Falha ao consultar o lote {batch_id}: {e}"),
+            }
+            if started.elapsed() > BATCH_TIMEOUT {
+                break format!(
+                    "Lote {batch_id} não foi avaliado em {} segundos",
+                    BATCH_TIMEOUT.as_secs()
+                );
+            }
+        };
+        // Sem o aviso, os jobs ainda pendentes do lote continuariam sendo distribuídos.
+        let cancel = Request::CancelBatch {
+            worker_id: self.worker_id,
+            batch_id,
+        };
+        if let Err(e) = self.request(&cancel) {
+            warn!("Falha ao cancelar o lote {batch_id} no host: {e}");
         }
+        Err(error)
     }
 }
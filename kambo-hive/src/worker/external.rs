@@ -0,0 +1,268 @@
+use std::{
+    io::{Read, Write},
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::common::{GARunner, Problem, Task, TaskResult, TracePoint};
+
+/// Como a task é entregue ao processo externo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputMode {
+    /// JSON da task escrito na entrada padrão.
+    Stdin,
+    /// JSON da task passado como último argumento.
+    Argument,
+}
+
+/// Entrada enviada ao processo externo.
+#[derive(Debug, Serialize)]
+pub struct ExternalTaskInput<'a> {
+    pub task_id: Uuid,
+    pub graph_id: &'a str,
+    pub graph_path: String,
+    pub algorithm: &'a str,
+    pub problem: Problem,
+    pub run_number: u32,
+    pub seed: u64,
+    pub config: &'a serde_json::Value,
+}
+
+/// Resposta esperada na saída padrão do processo externo.
+#[derive(Debug, Deserialize)]
+pub struct ExternalTaskOutput {
+    pub fitness: f64,
+    #[serde(default)]
+    pub solution_data: Vec<u8>,
+    #[serde(default)]
+    pub interations_run: u32,
+    #[serde(default)]
+    pub lower_bound: Option<f64>,
+    #[serde(default)]
+    pub optimal: bool,
+    /// Curva de convergência opcional, com `elapsed_ms`, `iteration` e `best_fitness`.
+    #[serde(default)]
+    pub trace: Vec<TracePoint>,
+}
+
+/// Runner que executa um solver externo por task, em qualquer linguagem.
+///
+/// O processo recebe um `ExternalTaskInput` em JSON e deve escrever um
+/// `ExternalTaskOutput` em JSON na saída padrão (a última linha não vazia é usada).
+#[derive(Debug, Clone)]
+pub struct ExternalProcessRunner {
+    pub program: String,
+    pub args: Vec<String>,
+    pub graphs_path: String,
+    pub input_mode: InputMode,
+    pub timeout: Option<Duration>,
+    /// Limite do espaço de endereçamento do processo (`RLIMIT_AS`). Ignorado fora de Unix.
+    pub memory_limit_bytes: Option<u64>,
+}
+
+impl ExternalProcessRunner {
+    #[must_use]
+    pub fn new(program: &str, args: Vec<String>, graphs_path: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            args,
+            graphs_path: graphs_path.to_string(),
+            input_mode: InputMode::Stdin,
+            timeout: None,
+            memory_limit_bytes: None,
+        }
+    }
+
+    fn build_command(&self, input_json: &str) -> Command {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if self.input_mode == InputMode::Argument {
+            command.arg(input_json);
+        }
+        self.apply_memory_limit(&mut command);
+        command
+    }
+
+    #[cfg(unix)]
+    fn apply_memory_limit(&self, command: &mut Command) {
+        use std::os::unix::process::CommandExt;
+
+        if let Some(bytes) = self.memory_limit_bytes {
+            let limit = libc::rlimit {
+                rlim_cur: bytes as libc::rlim_t,
+                rlim_max: bytes as libc::rlim_t,
+            };
+            // SAFETY: setrlimit é async-signal-safe e só afeta o processo filho.
+            unsafe {
+                command.pre_exec(move || {
+                    if libc::setrlimit(libc::RLIMIT_AS, &limit) == 0 {
+                        Ok(())
+                    } else {
+                        Err(std::io::Error::last_os_error())
+                    }
+                });
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn apply_memory_limit(&self, _command: &mut Command) {
+        if self.memory_limit_bytes.is_some() {
+            warn!("Limite de memória não suportado nesta plataforma, ignorando.");
+        }
+    }
+
+    fn wait_with_timeout(&self, child: &mut Child) -> Result<ExitStatus, String> {
+        let start = Instant::now();
+        loop {
+            if let Some(status) = child
+                .try_wait()
+                .map_err(|e| format!("Falha ao aguardar o processo '{}': {e}", self.program))?
+            {
+                return Ok(status);
+            }
+            if let Some(timeout) = self.timeout
+                && start.elapsed() >= timeout
+            {
+                warn!(
+                    "Processo '{}' excedeu o tempo limite de {:?}, encerrando.",
+                    self.program, timeout
+                );
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "Processo '{}' excedeu o tempo limite de {timeout:?}",
+                    self.program
+                ));
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+fn spawn_reader<R: Read + Send + 'static>(mut source: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = String::new();
+        let _ = source.read_to_string(&mut buffer);
+        buffer
+    })
+}
+
+/// Escreve `input` em outra thread: um processo que não lê a entrada enche o pipe e
+/// travaria a escrita antes de `wait_with_timeout` começar a contar o tempo.
+fn spawn_writer<W: Write + Send + 'static>(
+    mut sink: W,
+    input: String,
+    program: String,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        // Um processo que termina sem ler a entrada não é um erro por si só.
+        if let Err(e) = sink.write_all(input.as_bytes()) {
+            debug!("Falha ao escrever na entrada do processo '{program}': {e}");
+        }
+    })
+}
+
+impl GARunner for ExternalProcessRunner {
+    type Config = serde_json::Value;
+
+    fn run(
+        &self,
+        task: Task,
+        config: serde_json::Value,
+        worker_id: Uuid,
+    ) -> Result<TaskResult, String> {
+        let graph_path = Path::new(&self.graphs_path)
+            .join(&task.graph_id)
+            .to_string_lossy()
+            .to_string();
+        let input = ExternalTaskInput {
+            task_id: task.id,
+            graph_id: &task.graph_id,
+            graph_path,
+            algorithm: &task.algorithm,
+            problem: task.problem,
+            run_number: task.run_number,
+            seed: task.seed,
+            config: &config,
+        };
+        let input_json = serde_json::to_string(&input).map_err(|e| e.to_string())?;
+
+        info!(
+            "Worker {worker_id} executando '{}' para a task {}",
+            self.program, task.id
+        );
+        let start_time = Instant::now();
+        let mut child = self
+            .build_command(&input_json)
+            .spawn()
+            .map_err(|e| format!("Falha ao iniciar o processo '{}': {e}", self.program))?;
+
+        let stdout = spawn_reader(child.stdout.take().expect("stdout configurado como pipe"));
+        let stderr = spawn_reader(child.stderr.take().expect("stderr configurado como pipe"));
+        let stdin = child
+            .stdin
+            .take()
+            .filter(|_| self.input_mode == InputMode::Stdin)
+            .map(|stdin| spawn_writer(stdin, input_json, self.program.clone()));
+
+        // Se o processo for morto pelo tempo limite, o pipe fecha e a escrita termina sozinha.
+        let status = self.wait_with_timeout(&mut child)?;
+        if let Some(stdin) = stdin {
+            let _ = stdin.join();
+        }
+        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        if !status.success() {
+            return Err(format!(
+                "Processo '{}' terminou com {status}. stderr: {}",
+                self.program,
+                stderr.trim()
+            ));
+        }
+        if !stderr.trim().is_empty() {
+            debug!("stderr de '{}': {}", self.program, stderr.trim());
+        }
+
+        let answer = stdout
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .ok_or_else(|| format!("Processo '{}' não escreveu nenhuma resposta", self.program))?;
+        let output: ExternalTaskOutput = serde_json::from_str(answer).map_err(|e| {
+            format!(
+                "Resposta inválida do processo '{}': {e}. Saída: {answer}",
+                self.program
+            )
+        })?;
+
+        Ok(TaskResult {
+            task_id: task.id,
+            graph_id: task.graph_id,
+            algorithm: task.algorithm,
+            configuration: task.configuration,
+            problem: task.problem,
+            seed: task.seed,
+            worker_id,
+            fitness: output.fitness,
+            solution_data: output.solution_data,
+            interations_run: output.interations_run,
+            processing_time_ms,
+            lower_bound: output.lower_bound,
+            optimal: output.optimal,
+            trace: output.trace,
+        })
+    }
+}
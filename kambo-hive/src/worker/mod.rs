@@ -1 +1,3 @@
 pub mod client;
+pub mod external;
+pub mod registry;
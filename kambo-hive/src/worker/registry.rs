@@ -0,0 +1,140 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use uuid::Uuid;
+
+use crate::common::{
+    EvaluationJob, FitnessEvaluator, GARunner, HostChannel, Problem, Task, TaskResult,
+};
+
+trait ErasedRunner: Send + Sync {
+    fn supported_problems(&self) -> Vec<Problem>;
+
+    fn run_task(
+        &self,
+        task: Task,
+        worker_id: Uuid,
+        host: &mut dyn HostChannel,
+    ) -> Result<TaskResult, String>;
+}
+
+impl<T: GARunner> ErasedRunner for T {
+    fn supported_problems(&self) -> Vec<Problem> {
+        self.problems()
+    }
+
+    fn run_task(
+        &self,
+        task: Task,
+        worker_id: Uuid,
+        host: &mut dyn HostChannel,
+    ) -> Result<TaskResult, String> {
+        let config = task.config::<T::Config>()?;
+        let problems = self.problems();
+        if !problems.contains(&task.problem) {
+            let problems: Vec<String> = problems.iter().map(ToString::to_string).collect();
+            return Err(format!(
+                "Task {}: o algoritmo '{}' não resolve o problema '{}' (aceita: {})",
+                task.id,
+                task.algorithm,
+                task.problem,
+                problems.join(", ")
+            ));
+        }
+        self.check(&task, &config)
+            .map_err(|e| format!("Task {}: {e}", task.id))?;
+        self.run_cooperative(task, config, worker_id, host)
+    }
+}
+
+/// Conjunto de algoritmos que um worker sabe executar, indexados pelo nome
+/// usado em `Task::algorithm`, e de avaliadores do modo mestre-escravo,
+/// indexados pelo nome usado em `EvaluationJob::evaluator`.
+#[derive(Default, Clone)]
+pub struct RunnerRegistry {
+    runners: BTreeMap<String, Arc<dyn ErasedRunner>>,
+    evaluators: BTreeMap<String, Arc<dyn FitnessEvaluator>>,
+}
+
+impl RunnerRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra `runner` sob `name`, substituindo um registro anterior com o mesmo nome.
+    pub fn register<T: GARunner>(&mut self, name: &str, runner: T) {
+        self.runners.insert(name.to_string(), Arc::new(runner));
+    }
+
+    /// Registra `evaluator` sob `name`, substituindo um registro anterior com o mesmo nome.
+    pub fn register_evaluator<E: FitnessEvaluator>(&mut self, name: &str, evaluator: E) {
+        self.evaluators
+            .insert(name.to_string(), Arc::new(evaluator));
+    }
+
+    #[must_use]
+    pub fn evaluators(&self) -> Vec<String> {
+        self.evaluators.keys().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn algorithms(&self) -> Vec<String> {
+        self.runners.keys().cloned().collect()
+    }
+
+    /// Variantes do problema que cada algoritmo registrado resolve.
+    #[must_use]
+    pub fn problems(&self) -> BTreeMap<String, Vec<Problem>> {
+        self.runners
+            .iter()
+            .map(|(name, runner)| (name.clone(), runner.supported_problems()))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn supports(&self, algorithm: &str) -> bool {
+        self.runners.contains_key(algorithm)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.runners.is_empty() && self.evaluators.is_empty()
+    }
+
+    /// Executa `task`; `host` é usado pelos runners que cooperam durante a execução.
+    pub fn run(
+        &self,
+        task: Task,
+        worker_id: Uuid,
+        host: &mut dyn HostChannel,
+    ) -> Result<TaskResult, String> {
+        let runner = self.runners.get(&task.algorithm).ok_or_else(|| {
+            format!(
+                "Algoritmo '{}' não registrado neste worker (disponíveis: {:?})",
+                task.algorithm,
+                self.algorithms()
+            )
+        })?;
+        runner.run_task(task, worker_id, host)
+    }
+
+    pub fn evaluate(&self, job: &EvaluationJob) -> Result<Vec<f64>, String> {
+        let evaluator = self.evaluators.get(&job.evaluator).ok_or_else(|| {
+            format!(
+                "Avaliador '{}' não registrado neste worker (disponíveis: {:?})",
+                job.evaluator,
+                self.evaluators()
+            )
+        })?;
+        let fitnesses = evaluator.evaluate(job)?;
+        if fitnesses.len() != job.individuals.len() {
+            return Err(format!(
+                "Avaliador '{}' devolveu {} fitness para {} indivíduos",
+                job.evaluator,
+                fitnesses.len(),
+                job.individuals.len()
+            ));
+        }
+        Ok(fitnesses)
+    }
+}
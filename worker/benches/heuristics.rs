@@ -0,0 +1,109 @@
+//! Compara as heurísticas de `Graph` (listas de adjacência) com as de `CsrGraph`.
+//!
+//! Uso: `cargo bench -p kambo-hive-worker --bench heuristics [-- <arquivos de grafo>]`.
+//! Sem argumentos, usa grafos aleatórios; as versões antigas só rodam nos menores,
+//! já que `h2`..`h4` são quadráticas.
+
+use std::{
+    collections::HashSet,
+    env,
+    time::{Duration, Instant},
+};
+
+use kambo_hive_worker::graph::{CsrGraph, Graph};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const OLD_MAX_VERTICES: usize = 5_000;
+
+fn random_graph(
+    num_vertices: usize,
+    average_degree: usize,
+    seed: u64,
+) -> (usize, Vec<(usize, usize)>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut seen = HashSet::new();
+    let mut edges = Vec::new();
+    while edges.len() < num_vertices * average_degree / 2 {
+        let u = rng.random_range(0..num_vertices);
+        let v = rng.random_range(0..num_vertices);
+        if u != v && seen.insert((u.min(v), u.max(v))) {
+            edges.push((u, v));
+        }
+    }
+    (num_vertices, edges)
+}
+
+fn time<F: FnMut() -> Vec<u8>>(mut heuristic: F) -> (Duration, usize) {
+    let start = Instant::now();
+    let labels = heuristic();
+    (start.elapsed(), Graph::roman_weight(&labels))
+}
+
+fn bench(name: &str, graph: &Graph) {
+    let csr = CsrGraph::from(graph);
+    println!("\n{name}: {} vértices", graph.get_num_vertices());
+    println!(
+        "{:<4} {:>14} {:>10} {:>14} {:>10}",
+        "", "Graph", "peso", "CsrGraph", "peso"
+    );
+
+    let run_old = graph.get_num_vertices() <= OLD_MAX_VERTICES;
+    let old: [&dyn Fn() -> Vec<u8>; 4] = [&|| graph.h1(), &|| graph.h2(), &|| graph.h3(), &|| {
+        graph.h4()
+    }];
+    let new: [&dyn Fn() -> Vec<u8>; 4] = [&|| csr.h1(), &|| csr.h2(), &|| csr.h3(), &|| csr.h4()];
+
+    for (index, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+        let (new_time, new_weight) = time(new);
+        let new_labels = new();
+        assert!(
+            graph.is_roman_dominating(&new_labels),
+            "h{} (CSR) inviável",
+            index + 1
+        );
+
+        if run_old {
+            let (old_time, old_weight) = time(old);
+            println!(
+                "h{:<3} {:>14.3?} {:>10} {:>14.3?} {:>10}",
+                index + 1,
+                old_time,
+                old_weight,
+                new_time,
+                new_weight
+            );
+        } else {
+            println!(
+                "h{:<3} {:>14} {:>10} {:>14.3?} {:>10}",
+                index + 1,
+                "-",
+                "-",
+                new_time,
+                new_weight
+            );
+        }
+    }
+}
+
+fn main() {
+    let files: Vec<String> = env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    if files.is_empty() {
+        for (num_vertices, average_degree) in [(1_000, 6), (5_000, 6), (100_000, 6), (1_000_000, 6)]
+        {
+            let (n, edges) = random_graph(num_vertices, average_degree, 42);
+            bench(
+                &format!("aleatório (grau médio {average_degree})"),
+                &Graph::new(n, &edges),
+            );
+        }
+    } else {
+        for file in files {
+            let graph = Graph::from_file(&file).expect("falha ao carregar o grafo");
+            bench(&file, &graph);
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    graph::{CsrGraph, Graph},
+    local_search::{self, Strategy},
+};
+
+/// Maior componente conexa que o branch and bound resolve (uma máscara `u128` por vértice).
+pub const MAX_EXACT_COMPONENT: usize = 128;
+
+/// Solução do solver exato. Se `optimal` for falso (tempo esgotado ou componente
+/// grande demais), `weight` é a melhor solução encontrada e `lower_bound` um limite inferior.
+pub struct ExactResult {
+    pub solution: Vec<u8>,
+    pub weight: usize,
+    pub lower_bound: usize,
+    pub optimal: bool,
+    pub nodes_explored: usize,
+}
+
+/// Limite inferior γR ≥ 2n/(Δ+1), aplicado a cada componente conexa.
+///
+/// Cada vértice com rótulo 2 domina no máximo Δ+1 vértices e cada vértice com
+/// rótulo 1 só a si mesmo; vértices isolados contribuem com 1.
+#[must_use]
+pub fn degree_lower_bound(graph: &Graph) -> usize {
+    graph
+        .connected_components()
+        .iter()
+        .map(|component| component_degree_bound(graph, component))
+        .sum()
+}
+
+fn component_degree_bound(graph: &Graph, component: &[usize]) -> usize {
+    let max_degree = component
+        .iter()
+        .map(|&vertex| graph.get_vertex_degree(vertex))
+        .max()
+        .unwrap_or(0);
+    if max_degree == 0 {
+        component.len()
+    } else {
+        (2 * component.len()).div_ceil(max_degree + 1)
+    }
+}
+
+/// Melhor solução heurística (`h1`..`h4` seguidas de busca local), usada como limite superior.
+fn heuristic_solution(graph: &Graph) -> Vec<u8> {
+    let csr = CsrGraph::from(graph);
+    [csr.h1(), csr.h2(), csr.h3(), csr.h4()]
+        .into_iter()
+        .map(|labels| local_search::improve(graph, labels, Strategy::FirstImprovement))
+        .min_by_key(|labels| Graph::roman_weight(labels))
+        .unwrap_or_default()
+}
+
+/// Resolve cada componente conexa por branch and bound até `time_limit`.
+#[must_use]
+pub fn solve(graph: &Graph, time_limit: Duration) -> ExactResult {
+    let deadline = Instant::now() + time_limit;
+    let mut solution = heuristic_solution(graph);
+    let mut lower_bound = 0;
+    let mut optimal = true;
+    let mut nodes_explored = 0;
+
+    for component in graph.connected_components() {
+        let component_bound = component_degree_bound(graph, &component);
+        if component.len() > MAX_EXACT_COMPONENT {
+            optimal = false;
+            lower_bound += component_bound;
+            continue;
+        }
+
+        let mut search = ComponentSearch::new(graph, &component, &solution, deadline);
+        search.branch(0, 0, 0, 0, 0);
+        nodes_explored += search.nodes;
+
+        if search.timed_out {
+            optimal = false;
+            lower_bound += component_bound;
+        } else {
+            lower_bound += search.best_cost;
+        }
+        for (local, &vertex) in component.iter().enumerate() {
+            solution[vertex] = if search.best_twos & (1 << local) != 0 {
+                2
+            } else {
+                u8::from(search.best_ones & (1 << local) != 0)
+            };
+        }
+    }
+
+    let weight = Graph::roman_weight(&solution);
+    ExactResult {
+        solution,
+        weight,
+        lower_bound: lower_bound.min(weight),
+        optimal,
+        nodes_explored,
+    }
+}
+
+/// Branch and bound sobre uma componente, com vértices locais `0..k` em máscaras `u128`.
+///
+/// Escolhe o vértice ainda não resolvido com menos candidatos para dominá-lo e
+/// ramifica em "o candidato `u_i` recebe 2 (e `u_1..u_{i-1}` não)" ou, por último,
+/// "o vértice recebe 1 e nenhum candidato recebe 2".
+struct ComponentSearch {
+    closed: Vec<u128>,
+    all: u128,
+    best_cost: usize,
+    best_twos: u128,
+    best_ones: u128,
+    deadline: Instant,
+    nodes: usize,
+    timed_out: bool,
+}
+
+impl ComponentSearch {
+    fn new(graph: &Graph, component: &[usize], initial: &[u8], deadline: Instant) -> Self {
+        let local = |vertex: usize| component.binary_search(&vertex).unwrap();
+        let closed: Vec<u128> = component
+            .iter()
+            .enumerate()
+            .map(|(index, &vertex)| {
+                graph
+                    .get_neighbors(vertex)
+                    .iter()
+                    .fold(1u128 << index, |mask, &neighbor| {
+                        mask | 1 << local(neighbor)
+                    })
+            })
+            .collect();
+
+        let mut best_twos = 0u128;
+        let mut best_ones = 0u128;
+        for (index, &vertex) in component.iter().enumerate() {
+            match initial[vertex] {
+                2 => best_twos |= 1 << index,
+                1 => best_ones |= 1 << index,
+                _ => {}
+            }
+        }
+        let best_cost = 2 * best_twos.count_ones() as usize + best_ones.count_ones() as usize;
+
+        Self {
+            all: if component.len() == 128 {
+                u128::MAX
+            } else {
+                (1 << component.len()) - 1
+            },
+            closed,
+            best_cost,
+            best_twos,
+            best_ones,
+            deadline,
+            nodes: 0,
+            timed_out: false,
+        }
+    }
+
+    /// Custo mínimo para resolver os vértices pendentes: os que não têm candidato
+    /// custam 1; os demais custam pelo menos min(1, 2 / maior cobertura possível).
+    fn bound(&self, resolved: u128, forbidden: u128) -> usize {
+        let pending = self.all & !resolved;
+        let mut forced = 0;
+        let mut free = 0usize;
+        let mut max_cover = 0;
+        for vertex in bits(pending) {
+            if self.closed[vertex] & !forbidden == 0 {
+                forced += 1;
+            } else {
+                free += 1;
+            }
+        }
+        for candidate in bits(self.all & !forbidden) {
+            max_cover = max_cover.max((self.closed[candidate] & pending).count_ones() as usize);
+        }
+        forced + (2 * free).div_ceil(max_cover.max(2))
+    }
+
+    fn branch(&mut self, resolved: u128, forbidden: u128, twos: u128, ones: u128, cost: usize) {
+        if self.timed_out {
+            return;
+        }
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(4096) && Instant::now() >= self.deadline {
+            self.timed_out = true;
+            return;
+        }
+        if resolved == self.all {
+            if cost < self.best_cost {
+                self.best_cost = cost;
+                self.best_twos = twos;
+                self.best_ones = ones;
+            }
+            return;
+        }
+        if cost + self.bound(resolved, forbidden) >= self.best_cost {
+            return;
+        }
+
+        let vertex = bits(self.all & !resolved)
+            .min_by_key(|&vertex| (self.closed[vertex] & !forbidden).count_ones())
+            .unwrap();
+        let mut candidates: Vec<usize> = bits(self.closed[vertex] & !forbidden).collect();
+        candidates.sort_by_key(|&candidate| {
+            std::cmp::Reverse((self.closed[candidate] & !resolved).count_ones())
+        });
+
+        let mut excluded = forbidden;
+        for candidate in candidates {
+            self.branch(
+                resolved | self.closed[candidate],
+                excluded | 1 << candidate,
+                twos | 1 << candidate,
+                ones,
+                cost + 2,
+            );
+            excluded |= 1 << candidate;
+        }
+        self.branch(
+            resolved | 1 << vertex,
+            excluded,
+            twos,
+            ones | 1 << vertex,
+            cost + 1,
+        );
+    }
+}
+
+fn bits(mut mask: u128) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            None
+        } else {
+            let bit = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+            Some(bit)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::generators;
+
+    const TIME_LIMIT: Duration = Duration::from_secs(10);
+
+    fn path(n: usize) -> Graph {
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        Graph::new(n, &edges)
+    }
+
+    fn cycle(n: usize) -> Graph {
+        let mut edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        edges.push((n - 1, 0));
+        Graph::new(n, &edges)
+    }
+
+    fn star(leaves: usize) -> Graph {
+        let edges: Vec<(usize, usize)> = (1..=leaves).map(|leaf| (0, leaf)).collect();
+        Graph::new(leaves + 1, &edges)
+    }
+
+    fn complete(n: usize) -> Graph {
+        let edges: Vec<(usize, usize)> = (0..n)
+            .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+            .collect();
+        Graph::new(n, &edges)
+    }
+
+    /// γR por força bruta: para cada conjunto `T` de vértices com rótulo 2, os
+    /// vértices fora de `N[T]` recebem 1.
+    fn brute_force(graph: &Graph) -> usize {
+        let n = graph.get_num_vertices();
+        (0u32..1 << n)
+            .map(|twos| {
+                let undominated = (0..n)
+                    .filter(|&v| {
+                        twos & 1 << v == 0
+                            && !graph.get_neighbors(v).iter().any(|&u| twos & 1 << u != 0)
+                    })
+                    .count();
+                2 * twos.count_ones() as usize + undominated
+            })
+            .min()
+            .unwrap()
+    }
+
+    fn assert_optimal(graph: &Graph) -> ExactResult {
+        let expected = brute_force(graph);
+        let result = solve(graph, TIME_LIMIT);
+        assert!(result.optimal);
+        assert_eq!(result.weight, expected);
+        assert_eq!(result.lower_bound, expected);
+        assert_eq!(Graph::roman_weight(&result.solution), expected);
+        assert!(graph.is_roman_dominating(&result.solution));
+        assert!(degree_lower_bound(graph) <= expected);
+        result
+    }
+
+    #[test]
+    fn paths_and_cycles_match_brute_force() {
+        for n in 1..=12 {
+            let result = assert_optimal(&path(n));
+            // γR(P_n) = ⌈2n/3⌉.
+            assert_eq!(result.weight, (2 * n).div_ceil(3), "P_{n}");
+        }
+        for n in 3..=12 {
+            let result = assert_optimal(&cycle(n));
+            // γR(C_n) = ⌈2n/3⌉.
+            assert_eq!(result.weight, (2 * n).div_ceil(3), "C_{n}");
+        }
+    }
+
+    #[test]
+    fn stars_and_complete_graphs_match_brute_force() {
+        for leaves in 1..=11 {
+            let result = assert_optimal(&star(leaves));
+            assert_eq!(result.weight, 2, "K_1,{leaves}");
+        }
+        for n in 1..=12 {
+            let result = assert_optimal(&complete(n));
+            assert_eq!(result.weight, n.min(2), "K_{n}");
+        }
+    }
+
+    #[test]
+    fn random_graphs_match_brute_force() {
+        for n in 1..=12 {
+            for (seed, p) in [0.15, 0.3, 0.5, 0.8].into_iter().enumerate() {
+                assert_optimal(&generators::erdos_renyi(n, p, seed as u64));
+            }
+        }
+    }
+
+    #[test]
+    fn components_are_solved_separately() {
+        // P_4 + K_3 + vértice isolado + K_1,3: 3 + 2 + 1 + 2.
+        let mut edges = vec![(0, 1), (1, 2), (2, 3), (4, 5), (5, 6), (4, 6)];
+        edges.extend([(8, 9), (8, 10), (8, 11)]);
+        let graph = Graph::new(12, &edges);
+        assert_eq!(graph.connected_components().len(), 4);
+        let result = assert_optimal(&graph);
+        assert_eq!(result.weight, 8);
+        // 2·4/3, 2·3/3, o vértice isolado e 2·4/4.
+        assert_eq!(degree_lower_bound(&graph), 3 + 2 + 1 + 2);
+    }
+
+    #[test]
+    fn large_component_keeps_degree_bound() {
+        // P_130 passa de `MAX_EXACT_COMPONENT`; o triângulo ainda é resolvido.
+        let n = MAX_EXACT_COMPONENT + 2;
+        let mut edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        edges.extend([(n, n + 1), (n + 1, n + 2), (n, n + 2)]);
+        let graph = Graph::new(n + 3, &edges);
+
+        let result = solve(&graph, TIME_LIMIT);
+        assert!(!result.optimal);
+        assert!(graph.is_roman_dominating(&result.solution));
+        assert_eq!(result.lower_bound, (2 * n).div_ceil(3) + 2);
+        assert!(result.weight >= result.lower_bound);
+    }
+}
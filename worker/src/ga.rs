@@ -0,0 +1,347 @@
+use std::time::{Duration, Instant};
+
+use kambo_hive::common::{GAConfig, HostChannel, Migrant, TracePoint, TraceRecorder};
+use log::{debug, warn};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    graph::{CsrGraph, Graph},
+    local_search::{self, Strategy},
+    variant::RomanVariant,
+};
+
+/// Melhor solução encontrada pelo AG e quantas gerações foram executadas.
+pub struct GAResult {
+    pub solution: Vec<u8>,
+    pub fitness: usize,
+    pub generations_run: usize,
+    /// Melhor fitness a cada melhora, se `GAConfig::trace_points` estiver definido.
+    pub trace: Vec<TracePoint>,
+}
+
+/// Migração do modelo de ilhas: a cada `interval` gerações os `count` melhores
+/// indivíduos são enviados ao host, e os imigrantes recebidos substituem os piores.
+pub struct Migration {
+    pub interval: usize,
+    pub count: usize,
+}
+
+#[derive(Clone)]
+struct Individual {
+    labels: Vec<u8>,
+    fitness: usize,
+}
+
+/// Algoritmo genético para as variantes de dominação romana.
+///
+/// População inicial a partir das heurísticas `h1`..`h4` (convertidas para a
+/// variante com `RomanVariant::lift_roman`), seleção por torneio,
+/// crossover de um ponto, mutação por vértice e reparo das soluções inviáveis,
+/// opcionalmente seguido de busca local (`GAConfig::local_search`). Para após `generations` gerações,
+/// `max_stagnant` gerações sem melhora ou `time_limit_secs` segundos.
+/// A busca local só está disponível para `Problem::Roman`.
+///
+/// Com `GAConfig::evaluator` e um canal com o host, os filhos de cada geração são
+/// avaliados por outros workers (modo mestre-escravo); se o cluster não responder,
+/// os filhos daquela geração são avaliados localmente e a geração seguinte tenta o
+/// cluster de novo.
+pub struct GeneticAlgorithm<'a> {
+    graph: &'a Graph,
+    variant: &'a dyn RomanVariant,
+    config: &'a GAConfig,
+    rng: StdRng,
+}
+
+impl<'a> GeneticAlgorithm<'a> {
+    #[must_use]
+    pub fn new(
+        graph: &'a Graph,
+        variant: &'a dyn RomanVariant,
+        config: &'a GAConfig,
+        seed: u64,
+    ) -> Self {
+        Self {
+            graph,
+            variant,
+            config,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn pop_size(&self) -> usize {
+        self.config
+            .pop_size
+            .unwrap_or_else(|| (self.graph.get_num_vertices() / 2).clamp(10, 100))
+    }
+
+    pub fn run(&mut self) -> GAResult {
+        self.evolve(None, None)
+    }
+
+    /// Executa o AG usando o host para avaliar os filhos (se `GAConfig::evaluator`
+    /// estiver definido) e, com `migration`, como uma ilha que troca indivíduos.
+    pub fn run_cooperative(
+        &mut self,
+        host: &mut dyn HostChannel,
+        migration: Option<&Migration>,
+    ) -> GAResult {
+        self.evolve(Some(host), migration)
+    }
+
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn evolve(
+        &mut self,
+        mut host: Option<&mut (dyn HostChannel + '_)>,
+        migration: Option<&Migration>,
+    ) -> GAResult {
+        let pop_size = self.pop_size();
+        let mut population = self.initial_population(pop_size);
+
+        let mut best_index = Self::best_index(&population);
+        let mut best_fitness = population[best_index].fitness;
+        let mut stagnant = 0;
+        let mut generations_run = 0;
+        let mut trace = self.config.trace_points.map(TraceRecorder::new);
+        if let Some(trace) = &mut trace {
+            trace.record(0, best_fitness as f64);
+        }
+        let deadline = self
+            .config
+            .time_limit_secs
+            .map(|secs| Instant::now() + Duration::from_secs(secs));
+
+        while generations_run < self.config.generations
+            && stagnant < self.config.max_stagnant
+            && deadline.is_none_or(|deadline| Instant::now() < deadline)
+        {
+            generations_run += 1;
+
+            let mut next = Vec::with_capacity(pop_size);
+            next.push(population[best_index].clone()); // elitismo
+
+            let mut offspring = Vec::with_capacity(pop_size - 1);
+            while offspring.len() + 1 < pop_size {
+                let first = self.tournament(&population);
+                let second = self.tournament(&population);
+                let mut labels = if self
+                    .rng
+                    .random_bool(f64::from(self.config.crossover_probability))
+                {
+                    self.crossover(&population[first].labels, &population[second].labels)
+                } else {
+                    population[first].labels.clone()
+                };
+                self.mutate(&mut labels);
+                self.variant.repair(self.graph, &mut labels);
+                offspring.push(labels);
+            }
+            next.extend(self.evaluate(offspring, host.as_deref_mut()));
+
+            population = next;
+            if let (Some(migration), Some(host)) = (migration, host.as_deref_mut()) {
+                if generations_run % migration.interval == 0 {
+                    self.migrate(&mut population, migration, host);
+                }
+            }
+            best_index = Self::best_index(&population);
+            if population[best_index].fitness < best_fitness {
+                best_fitness = population[best_index].fitness;
+                stagnant = 0;
+                if let Some(trace) = &mut trace {
+                    trace.record(generations_run as u32, best_fitness as f64);
+                }
+            } else {
+                stagnant += 1;
+            }
+        }
+
+        let best = population.swap_remove(best_index);
+        GAResult {
+            trace: trace.map_or_else(Vec::new, |trace| {
+                trace.finish(generations_run as u32, best.fitness as f64)
+            }),
+            solution: best.labels,
+            fitness: best.fitness,
+            generations_run,
+        }
+    }
+
+    /// Avalia os filhos no cluster quando possível e localmente caso contrário.
+    #[allow(clippy::cast_sign_loss)]
+    fn evaluate(
+        &self,
+        offspring: Vec<Vec<u8>>,
+        host: Option<&mut (dyn HostChannel + '_)>,
+    ) -> Vec<Individual> {
+        let offspring: Vec<Vec<u8>> = offspring
+            .into_iter()
+            .map(|labels| self.improve(labels))
+            .collect();
+        if let (Some(evaluator), Some(host)) = (&self.config.evaluator, host) {
+            match host.evaluate_batch(evaluator, offspring.clone()) {
+                Ok(fitnesses)
+                    if fitnesses.len() == offspring.len()
+                        && fitnesses.iter().all(|f| f.is_finite() && *f >= 0.0) =>
+                {
+                    return offspring
+                        .into_iter()
+                        .zip(fitnesses)
+                        .map(|(labels, fitness)| Individual {
+                            labels,
+                            fitness: fitness.round() as usize,
+                        })
+                        .collect();
+                }
+                Ok(fitnesses) => warn!(
+                    "Lote avaliado localmente: {} fitness inválidos para {} indivíduos",
+                    fitnesses.len(),
+                    offspring.len()
+                ),
+                // Recusas e lotes abandonados são comuns com o cluster ocupado; o host
+                // avisa quando um lote é cancelado.
+                Err(e) => debug!("Lote avaliado localmente: {e}"),
+            }
+        }
+        offspring
+            .into_iter()
+            .map(|labels| {
+                let fitness = self.variant.cost(&labels);
+                Individual { labels, fitness }
+            })
+            .collect()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn migrate(
+        &self,
+        population: &mut [Individual],
+        migration: &Migration,
+        host: &mut (dyn HostChannel + '_),
+    ) {
+        let mut order: Vec<usize> = (0..population.len()).collect();
+        order.sort_by_key(|&index| population[index].fitness);
+        let emigrants = order
+            .iter()
+            .take(migration.count)
+            .map(|&index| Migrant {
+                solution: population[index].labels.clone(),
+                fitness: population[index].fitness as f64,
+            })
+            .collect();
+        let immigrants = match host.exchange_migrants(emigrants) {
+            Ok(immigrants) => immigrants,
+            Err(e) => {
+                warn!("Migração ignorada: {e}");
+                return;
+            }
+        };
+
+        // Os piores indivíduos dão lugar aos imigrantes; o melhor nunca é substituído.
+        let valid = immigrants.into_iter().filter(|migrant| {
+            migrant.solution.len() == self.graph.get_num_vertices()
+                && migrant
+                    .solution
+                    .iter()
+                    .all(|&label| label <= self.variant.max_label())
+        });
+        for (migrant, &index) in valid.zip(order.iter().skip(1).rev()) {
+            let mut labels = migrant.solution;
+            self.variant.repair(self.graph, &mut labels);
+            population[index] = self.individual(labels);
+        }
+    }
+
+    fn initial_population(&mut self, pop_size: usize) -> Vec<Individual> {
+        let csr = CsrGraph::from(self.graph);
+        let seeds = [csr.h1_with_rng(&mut self.rng), csr.h2(), csr.h3(), csr.h4()]
+            .map(|labels| self.variant.lift_roman(self.graph, labels));
+
+        let mut population: Vec<Individual> = Vec::with_capacity(pop_size);
+        for i in 0..pop_size {
+            let mut labels = if i % seeds.len() == 0 && i > 0 {
+                // h1 é aleatória, então gera novos indivíduos diversos.
+                let labels = csr.h1_with_rng(&mut self.rng);
+                self.variant.lift_roman(self.graph, labels)
+            } else {
+                seeds[i % seeds.len()].clone()
+            };
+            if i >= seeds.len() {
+                self.mutate(&mut labels);
+                self.variant.repair(self.graph, &mut labels);
+            }
+            population.push(self.individual(labels));
+        }
+        population
+    }
+
+    fn improve(&self, labels: Vec<u8>) -> Vec<u8> {
+        if self.config.local_search {
+            local_search::improve(self.graph, labels, Strategy::FirstImprovement)
+        } else {
+            labels
+        }
+    }
+
+    fn individual(&self, labels: Vec<u8>) -> Individual {
+        let labels = self.improve(labels);
+        let fitness = self.variant.cost(&labels);
+        Individual { labels, fitness }
+    }
+
+    fn best_index(population: &[Individual]) -> usize {
+        population
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, individual)| individual.fitness)
+            .map(|(index, _)| index)
+            .expect("população não pode ser vazia")
+    }
+
+    fn tournament(&mut self, population: &[Individual]) -> usize {
+        (0..self.config.tournament_size)
+            .map(|_| self.rng.random_range(0..population.len()))
+            .min_by_key(|&index| population[index].fitness)
+            .expect("tournament_size é validado como maior que zero")
+    }
+
+    fn crossover(&mut self, first: &[u8], second: &[u8]) -> Vec<u8> {
+        if first.len() < 2 {
+            return first.to_vec();
+        }
+        let point = self.rng.random_range(1..first.len());
+        first[..point]
+            .iter()
+            .chain(&second[point..])
+            .copied()
+            .collect()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn mutate(&mut self, labels: &mut [u8]) {
+        if labels.is_empty() {
+            return;
+        }
+        let rate = 1.0 / labels.len() as f64;
+        for label in labels.iter_mut() {
+            if self.rng.random_bool(rate) {
+                *label = self.rng.random_range(0..=self.variant.max_label());
+            }
+        }
+    }
+}
+
+/// Restaura a viabilidade e remove rótulos redundantes.
+///
+/// Vértices com rótulo 0 ou 1 passam a 0 se têm um vizinho com rótulo 2 e a 1 caso contrário.
+pub fn repair(graph: &Graph, labels: &mut [u8]) {
+    for vertex in 0..labels.len() {
+        if labels[vertex] == 2 {
+            continue;
+        }
+        let dominated = graph
+            .get_neighbors(vertex)
+            .iter()
+            .any(|&neighbor| labels[neighbor] == 2);
+        labels[vertex] = u8::from(!dominated);
+    }
+}
@@ -1,10 +1,21 @@
+mod csr;
+mod format;
+pub mod generators;
+mod reduction;
+
 use std::{
     collections::HashSet,
     fs::File,
-    io::{self, BufRead},
+    io::{self, BufRead, BufWriter, Write},
+    path::Path,
 };
 
-use rand::{rng, seq::IteratorRandom};
+pub use csr::CsrGraph;
+pub use kambo_hive::common::GraphFormat;
+pub use reduction::{Kernel, ReductionStats};
+
+use kambo_hive::common::InstanceStats;
+use rand::{rng, seq::IteratorRandom, Rng};
 
 #[derive(Clone)]
 pub struct Graph {
@@ -23,7 +34,7 @@ impl Graph {
     }
 
     #[must_use]
-    pub fn get_num_vertices(&self) -> usize {
+    pub const fn get_num_vertices(&self) -> usize {
         self.adjacency_list.len()
     }
 
@@ -51,14 +62,85 @@ impl Graph {
         self.adjacency_list[v].push(u);
     }
 
+    /// Componentes conexas, cada uma com seus vértices em ordem crescente.
+    #[must_use]
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.adjacency_list.len();
+        let mut visited = vec![false; n];
+        let mut components = Vec::new();
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut component = vec![start];
+            let mut next = 0;
+            while next < component.len() {
+                let vertex = component[next];
+                next += 1;
+                for &neighbor in self.get_neighbors(vertex) {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        component.push(neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Estatísticas da instância para o catálogo do host.
+    #[must_use]
+    pub fn statistics(&self) -> InstanceStats {
+        let degrees: Vec<usize> = self.adjacency_list.iter().map(Vec::len).collect();
+        let component_sizes: Vec<usize> =
+            self.connected_components().iter().map(Vec::len).collect();
+        InstanceStats::from_degrees(&degrees, &component_sizes)
+    }
+
+    /// Verifica se `labels` é uma função de dominação romana: um rótulo em
+    /// {0, 1, 2} por vértice e todo vértice com rótulo 0 tem um vizinho com rótulo 2.
+    #[must_use]
+    pub fn is_roman_dominating(&self, labels: &[u8]) -> bool {
+        labels.len() == self.adjacency_list.len()
+            && labels.iter().all(|&label| label <= 2)
+            && self.undominated_vertices(labels).is_empty()
+    }
+
+    /// Vértices com rótulo 0 sem nenhum vizinho com rótulo 2.
+    #[must_use]
+    pub fn undominated_vertices(&self, labels: &[u8]) -> Vec<usize> {
+        (0..self.adjacency_list.len().min(labels.len()))
+            .filter(|&vertex| {
+                labels[vertex] == 0
+                    && !self
+                        .get_neighbors(vertex)
+                        .iter()
+                        .any(|&neighbor| labels.get(neighbor) == Some(&2))
+            })
+            .collect()
+    }
+
+    /// Peso da função, isto é, a soma dos rótulos.
+    #[must_use]
+    pub fn roman_weight(labels: &[u8]) -> usize {
+        labels.iter().map(|&label| usize::from(label)).sum()
+    }
+
     #[must_use]
     pub fn h1(&self) -> Vec<u8> {
+        self.h1_with_rng(&mut rng())
+    }
+
+    /// Igual a `h1`, mas sorteando os vértices com o gerador `rng` informado.
+    pub fn h1_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
         let mut f: Vec<u8> = vec![0; self.adjacency_list.len()];
         let mut unvisited: HashSet<usize> = (0..self.adjacency_list.len()).collect();
-        let mut rng = rng();
 
         while !unvisited.is_empty() {
-            let &u = unvisited.iter().choose(&mut rng).unwrap();
+            let &u = unvisited.iter().choose(rng).unwrap();
             f[u] = 2;
             unvisited.remove(&u);
 
@@ -211,28 +293,60 @@ impl Graph {
         f
     }
 
+    /// Carrega um grafo detectando o formato pelo cabeçalho ou pela extensão do arquivo.
     pub fn from_file(file_path: &str) -> io::Result<Self> {
         let file = File::open(file_path)?;
-        let reader = io::BufReader::new(file);
-        let mut g = Self::new(0, &[]);
-
-        for line in reader.lines() {
-            let line = line?;
-            let vertices: Vec<usize> = line
-                .split_whitespace()
-                .filter_map(|s| s.parse::<usize>().ok())
-                .collect();
+        let mut reader = io::BufReader::new(file);
+        let first_line = {
+            let buffer = reader.fill_buf()?;
+            let end = buffer
+                .iter()
+                .position(|&b| b == b'\n')
+                .unwrap_or(buffer.len());
+            String::from_utf8_lossy(&buffer[..end]).into_owned()
+        };
+        let format = GraphFormat::detect(Path::new(file_path), &first_line);
+        Self::parse(reader, format)
+            .map_err(|e| io::Error::new(e.kind(), format!("{file_path} ({format:?}): {e}")))
+    }
 
-            if vertices.len() == 2 {
-                let (u, v) = (vertices[0], vertices[1]);
-                g.add_vertex(u);
-                g.add_vertex(v);
-                if u != v {
-                    g.add_edge(u, v);
-                }
+    /// Carrega um grafo no formato `format` ou, se ele não for informado, no formato detectado.
+    pub fn load(file_path: &str, format: Option<GraphFormat>) -> io::Result<Self> {
+        format.map_or_else(
+            || Self::from_file(file_path),
+            |format| Self::from_file_with_format(file_path, format),
+        )
+    }
+
+    pub fn from_file_with_format(file_path: &str, format: GraphFormat) -> io::Result<Self> {
+        let file = File::open(file_path)?;
+        Self::parse(io::BufReader::new(file), format)
+            .map_err(|e| io::Error::new(e.kind(), format!("{file_path} ({format:?}): {e}")))
+    }
+
+    pub fn parse<R: BufRead>(reader: R, format: GraphFormat) -> io::Result<Self> {
+        let parsed = format::parse(reader, format)?;
+        Ok(Self::new(parsed.num_vertices, &parsed.edges))
+    }
+
+    /// Escreve o grafo no formato `GraphFormat::EdgeList`, uma aresta `u v` (u < v) por linha.
+    ///
+    /// Vértices isolados são escritos como laços `v v`, que o leitor ignora, para
+    /// que o número de vértices seja preservado.
+    pub fn write_edge_list<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        for (u, neighbors) in self.adjacency_list.iter().enumerate() {
+            if neighbors.is_empty() {
+                writeln!(writer, "{u} {u}")?;
+            }
+            for &v in neighbors.iter().filter(|&&v| u < v) {
+                writeln!(writer, "{u} {v}")?;
             }
         }
+        writer.flush()
+    }
 
-        Ok(g)
+    pub fn save_edge_list(&self, file_path: &Path) -> io::Result<()> {
+        self.write_edge_list(File::create(file_path)?)
     }
 }
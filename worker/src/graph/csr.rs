@@ -0,0 +1,246 @@
+use rand::{rng, seq::SliceRandom, Rng};
+
+use super::Graph;
+
+/// Grafo em formato CSR (compressed sparse row): os vizinhos do vértice `v`
+/// são `neighbors[offsets[v]..offsets[v + 1]]`.
+///
+/// As heurísticas `h1`..`h4` têm a mesma semântica das de `Graph`, mas usam
+/// marcações em vetor e filas de prioridade por balde, executando em tempo
+/// O(n + m) (a menos da ordenação por grau em `h2`).
+#[derive(Clone)]
+pub struct CsrGraph {
+    offsets: Vec<usize>,
+    neighbors: Vec<u32>,
+}
+
+impl CsrGraph {
+    #[must_use]
+    pub fn new(num_vertices: usize, edges: &[(usize, usize)]) -> Self {
+        let mut degrees = vec![0usize; num_vertices];
+        for &(u, v) in edges {
+            degrees[u] += 1;
+            degrees[v] += 1;
+        }
+
+        let mut offsets = Vec::with_capacity(num_vertices + 1);
+        offsets.push(0);
+        for degree in &degrees {
+            offsets.push(offsets.last().unwrap() + degree);
+        }
+
+        let mut next = offsets.clone();
+        let mut neighbors = vec![0u32; offsets[num_vertices]];
+        for &(u, v) in edges {
+            neighbors[next[u]] = v as u32;
+            next[u] += 1;
+            neighbors[next[v]] = u as u32;
+            next[v] += 1;
+        }
+
+        Self { offsets, neighbors }
+    }
+
+    #[must_use]
+    pub const fn get_num_vertices(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    #[must_use]
+    pub fn get_neighbors(&self, vertex: usize) -> &[u32] {
+        &self.neighbors[self.offsets[vertex]..self.offsets[vertex + 1]]
+    }
+
+    #[must_use]
+    pub fn get_vertex_degree(&self, vertex: usize) -> usize {
+        self.offsets[vertex + 1] - self.offsets[vertex]
+    }
+
+    #[must_use]
+    pub fn h1(&self) -> Vec<u8> {
+        self.h1_with_rng(&mut rng())
+    }
+
+    /// Percorrer uma permutação aleatória ignorando os visitados equivale a
+    /// sortear, a cada passo, um vértice entre os ainda não visitados.
+    pub fn h1_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        let mut order: Vec<usize> = (0..self.get_num_vertices()).collect();
+        order.shuffle(rng);
+        self.greedy_in_order(order)
+    }
+
+    #[must_use]
+    pub fn h2(&self) -> Vec<u8> {
+        let mut order: Vec<usize> = (0..self.get_num_vertices()).collect();
+        order.sort_by_key(|&vertex| std::cmp::Reverse(self.get_vertex_degree(vertex)));
+        self.greedy_in_order(order)
+    }
+
+    /// Rotula com 2 o próximo vértice não visitado de `order` e com 0 seus vizinhos
+    /// não visitados; o último vértice que sobrar sozinho recebe 1.
+    fn greedy_in_order(&self, order: Vec<usize>) -> Vec<u8> {
+        let n = self.get_num_vertices();
+        let mut f = vec![0u8; n];
+        let mut visited = vec![false; n];
+        let mut remaining = n;
+
+        for u in order {
+            if visited[u] {
+                continue;
+            }
+            f[u] = 2;
+            visited[u] = true;
+            remaining -= 1;
+
+            for &v in self.get_neighbors(u) {
+                let v = v as usize;
+                if !visited[v] {
+                    f[v] = 0;
+                    visited[v] = true;
+                    remaining -= 1;
+                }
+            }
+
+            if remaining == 1 {
+                let last = (0..n).find(|&vertex| !visited[vertex]).unwrap();
+                f[last] = 1;
+                visited[last] = true;
+                remaining = 0;
+            }
+            if remaining == 0 {
+                break;
+            }
+        }
+        f
+    }
+
+    #[must_use]
+    pub fn h3(&self) -> Vec<u8> {
+        self.max_residual_degree(false)
+    }
+
+    #[must_use]
+    pub fn h4(&self) -> Vec<u8> {
+        self.max_residual_degree(true)
+    }
+
+    /// `h3`/`h4`: rotula com 2 o vértice de maior grau residual e com 0 seus vizinhos
+    /// não visitados. Em `h4`, vértices que ficam sem vizinhos não visitados recebem 1.
+    fn max_residual_degree(&self, label_isolated: bool) -> Vec<u8> {
+        let n = self.get_num_vertices();
+        let mut f = vec![0u8; n];
+        let mut visited = vec![false; n];
+        let mut remaining = n;
+        let mut queue = BucketQueue::new(self);
+        let mut zero_degree: Vec<usize> = if label_isolated {
+            (0..n).filter(|&v| queue.degree[v] == 0).collect()
+        } else {
+            Vec::new()
+        };
+
+        while remaining > 0 {
+            let u = queue.pop_max(&visited).unwrap();
+            f[u] = 2;
+
+            let mut removed = vec![u];
+            for &v in self.get_neighbors(u) {
+                let v = v as usize;
+                if !visited[v] && v != u {
+                    f[v] = 0;
+                    removed.push(v);
+                }
+            }
+            for &vertex in &removed {
+                visited[vertex] = true;
+            }
+            remaining -= removed.len();
+            for &vertex in &removed {
+                for &w in self.get_neighbors(vertex) {
+                    let w = w as usize;
+                    if !visited[w] {
+                        queue.decrease(w);
+                        if label_isolated && queue.degree[w] == 0 {
+                            zero_degree.push(w);
+                        }
+                    }
+                }
+            }
+
+            if label_isolated {
+                for vertex in std::mem::take(&mut zero_degree) {
+                    if !visited[vertex] {
+                        f[vertex] = 1;
+                        visited[vertex] = true;
+                        remaining -= 1;
+                    }
+                }
+            }
+
+            if remaining == 1 {
+                let last = queue.pop_max(&visited).unwrap();
+                f[last] = 1;
+                visited[last] = true;
+                remaining = 0;
+            }
+        }
+        f
+    }
+}
+
+/// Fila de prioridade por grau residual com remoção preguiçosa: cada mudança de
+/// grau empilha uma nova entrada, e entradas desatualizadas são descartadas no `pop`.
+struct BucketQueue {
+    buckets: Vec<Vec<usize>>,
+    degree: Vec<usize>,
+    max: usize,
+}
+
+impl BucketQueue {
+    fn new(graph: &CsrGraph) -> Self {
+        let n = graph.get_num_vertices();
+        let degree: Vec<usize> = (0..n).map(|v| graph.get_vertex_degree(v)).collect();
+        let max = degree.iter().copied().max().unwrap_or(0);
+        let mut buckets = vec![Vec::new(); max + 1];
+        for (vertex, &d) in degree.iter().enumerate() {
+            buckets[d].push(vertex);
+        }
+        Self {
+            buckets,
+            degree,
+            max,
+        }
+    }
+
+    fn decrease(&mut self, vertex: usize) {
+        self.degree[vertex] -= 1;
+        self.buckets[self.degree[vertex]].push(vertex);
+    }
+
+    fn pop_max(&mut self, visited: &[bool]) -> Option<usize> {
+        loop {
+            while let Some(vertex) = self.buckets[self.max].pop() {
+                if !visited[vertex] && self.degree[vertex] == self.max {
+                    return Some(vertex);
+                }
+            }
+            if self.max == 0 {
+                return None;
+            }
+            self.max -= 1;
+        }
+    }
+}
+
+impl From<&Graph> for CsrGraph {
+    fn from(graph: &Graph) -> Self {
+        let n = graph.get_num_vertices();
+        let mut offsets = Vec::with_capacity(n + 1);
+        let mut neighbors = Vec::new();
+        offsets.push(0);
+        for vertex in 0..n {
+            neighbors.extend(graph.get_neighbors(vertex).iter().map(|&v| v as u32));
+            offsets.push(neighbors.len());
+        }
+        Self { offsets, neighbors }
+    }
+}
@@ -0,0 +1,492 @@
+use std::{
+    collections::HashSet,
+    io::{self, BufRead},
+};
+
+use kambo_hive::common::GraphFormat;
+
+/// Vértices e arestas lidos de um arquivo, sem laços nem arestas repetidas.
+pub struct ParsedGraph {
+    pub num_vertices: usize,
+    pub edges: Vec<(usize, usize)>,
+}
+
+struct EdgeCollector {
+    num_vertices: usize,
+    edges: Vec<(usize, usize)>,
+    seen: HashSet<(usize, usize)>,
+}
+
+impl EdgeCollector {
+    fn new(num_vertices: usize) -> Self {
+        Self {
+            num_vertices,
+            edges: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    fn add(&mut self, u: usize, v: usize) {
+        self.num_vertices = self.num_vertices.max(u.max(v) + 1);
+        if u != v && self.seen.insert((u.min(v), u.max(v))) {
+            self.edges.push((u, v));
+        }
+    }
+
+    fn finish(self) -> ParsedGraph {
+        ParsedGraph {
+            num_vertices: self.num_vertices,
+            edges: self.edges,
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn parse_error(line_number: usize, message: &str) -> io::Error {
+    invalid_data(&format!("linha {line_number}: {message}"))
+}
+
+fn parse_index(token: &str, line_number: usize) -> io::Result<usize> {
+    token
+        .parse()
+        .map_err(|_| parse_error(line_number, &format!("vértice inválido '{token}'")))
+}
+
+/// Converte um índice a partir de 1 para a partir de 0, verificando o limite `n` quando conhecido.
+fn one_based(index: usize, n: Option<usize>, line_number: usize) -> io::Result<usize> {
+    if index == 0 {
+        return Err(parse_error(
+            line_number,
+            "vértice 0 em formato indexado a partir de 1",
+        ));
+    }
+    if let Some(n) = n {
+        if index > n {
+            return Err(parse_error(
+                line_number,
+                &format!("vértice {index} fora do intervalo 1..={n}"),
+            ));
+        }
+    }
+    Ok(index - 1)
+}
+
+/// Linhas numeradas a partir de 1, sem quebras de linha.
+fn numbered_lines<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<(usize, String)>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(index, line)| line.map(|line| (index + 1, line)))
+}
+
+pub fn parse<R: BufRead>(reader: R, format: GraphFormat) -> io::Result<ParsedGraph> {
+    match format {
+        GraphFormat::EdgeList => parse_edge_list(reader, false),
+        GraphFormat::EdgeListOneBased => parse_edge_list(reader, true),
+        GraphFormat::Dimacs => parse_dimacs(reader),
+        GraphFormat::Metis => parse_metis(reader),
+        GraphFormat::MatrixMarket => parse_matrix_market(reader),
+    }
+}
+
+fn parse_edge_list<R: BufRead>(reader: R, is_one_based: bool) -> io::Result<ParsedGraph> {
+    let mut collector = EdgeCollector::new(0);
+    for line in numbered_lines(reader) {
+        let (line_number, line) = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('%') {
+            continue;
+        }
+
+        // Colunas extras (por exemplo, pesos) são ignoradas.
+        let mut tokens = line.split_whitespace();
+        let (Some(u), Some(v)) = (tokens.next(), tokens.next()) else {
+            return Err(parse_error(
+                line_number,
+                "esperado um par de vértices 'u v'",
+            ));
+        };
+        let (mut u, mut v) = (parse_index(u, line_number)?, parse_index(v, line_number)?);
+        if is_one_based {
+            u = one_based(u, None, line_number)?;
+            v = one_based(v, None, line_number)?;
+        }
+        collector.add(u, v);
+    }
+    Ok(collector.finish())
+}
+
+fn parse_dimacs<R: BufRead>(reader: R) -> io::Result<ParsedGraph> {
+    let mut declared: Option<usize> = None;
+    let mut collector = EdgeCollector::new(0);
+
+    for line in numbered_lines(reader) {
+        let (line_number, line) = line?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.first().copied() {
+            None | Some("c") => {}
+            Some("p") => {
+                if declared.is_some() {
+                    return Err(parse_error(line_number, "linha 'p' repetida"));
+                }
+                if tokens.len() < 3 {
+                    return Err(parse_error(line_number, "esperado 'p edge <n> <m>'"));
+                }
+                let n = parse_index(tokens[2], line_number)?;
+                declared = Some(n);
+                collector.num_vertices = n;
+            }
+            Some("e") => {
+                let Some(n) = declared else {
+                    return Err(parse_error(line_number, "aresta antes da linha 'p'"));
+                };
+                if tokens.len() < 3 {
+                    return Err(parse_error(line_number, "esperado 'e <u> <v>'"));
+                }
+                let u = one_based(parse_index(tokens[1], line_number)?, Some(n), line_number)?;
+                let v = one_based(parse_index(tokens[2], line_number)?, Some(n), line_number)?;
+                collector.add(u, v);
+            }
+            Some(other) => {
+                return Err(parse_error(
+                    line_number,
+                    &format!("tipo de linha DIMACS desconhecido '{other}'"),
+                ));
+            }
+        }
+    }
+
+    if declared.is_none() {
+        return Err(invalid_data("arquivo DIMACS sem linha 'p edge <n> <m>'"));
+    }
+    Ok(collector.finish())
+}
+
+fn parse_metis<R: BufRead>(reader: R) -> io::Result<ParsedGraph> {
+    // (n, valores a pular no início de cada linha, pesos nas arestas)
+    let mut header: Option<(usize, usize, bool)> = None;
+    let mut collector = EdgeCollector::new(0);
+    let mut vertex = 0;
+
+    for line in numbered_lines(reader) {
+        let (line_number, line) = line?;
+        if line.trim_start().starts_with('%') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        let Some((n, skipped, edge_weights)) = header else {
+            if tokens.is_empty() {
+                continue;
+            }
+            if tokens.len() < 2 {
+                return Err(parse_error(
+                    line_number,
+                    "esperado cabeçalho '<n> <m> [fmt]'",
+                ));
+            }
+            let n = parse_index(tokens[0], line_number)?;
+            // fmt tem até três dígitos: tamanhos dos vértices, pesos dos vértices, pesos das arestas.
+            let fmt = format!("{:0>3}", tokens.get(2).copied().unwrap_or("0"));
+            let flags = fmt.as_bytes();
+            if flags.len() != 3 || flags.iter().any(|flag| !matches!(flag, b'0' | b'1')) {
+                return Err(parse_error(
+                    line_number,
+                    &format!("fmt METIS inválido '{fmt}'"),
+                ));
+            }
+            let ncon = match tokens.get(3) {
+                Some(token) => parse_index(token, line_number)?,
+                None => 1,
+            };
+            let skipped = usize::from(flags[0] == b'1') + if flags[1] == b'1' { ncon } else { 0 };
+            header = Some((n, skipped, flags[2] == b'1'));
+            collector.num_vertices = n;
+            continue;
+        };
+
+        if vertex >= n {
+            if tokens.is_empty() {
+                continue;
+            }
+            return Err(parse_error(
+                line_number,
+                &format!("mais linhas de adjacência que os {n} vértices declarados"),
+            ));
+        }
+
+        // Uma linha vazia é um vértice isolado.
+        let mut neighbors = tokens.iter().skip(skipped);
+        while let Some(token) = neighbors.next() {
+            let neighbor = one_based(parse_index(token, line_number)?, Some(n), line_number)?;
+            collector.add(vertex, neighbor);
+            if edge_weights {
+                neighbors.next();
+            }
+        }
+        vertex += 1;
+    }
+
+    match header {
+        None => Err(invalid_data("arquivo METIS sem cabeçalho")),
+        Some((n, _, _)) if vertex < n => Err(invalid_data(&format!(
+            "esperadas {n} linhas de adjacência, encontradas {vertex}"
+        ))),
+        Some(_) => Ok(collector.finish()),
+    }
+}
+
+fn parse_matrix_market<R: BufRead>(reader: R) -> io::Result<ParsedGraph> {
+    let mut lines = numbered_lines(reader);
+
+    let (line_number, banner) = lines
+        .next()
+        .ok_or_else(|| parse_error(1, "arquivo Matrix Market vazio"))??;
+    let banner = banner.to_ascii_lowercase();
+    let banner_tokens: Vec<&str> = banner.split_whitespace().collect();
+    if banner_tokens.first() != Some(&"%%matrixmarket") || banner_tokens.get(1) != Some(&"matrix") {
+        return Err(parse_error(
+            line_number,
+            "esperado '%%MatrixMarket matrix ...'",
+        ));
+    }
+    if banner_tokens.get(2) != Some(&"coordinate") {
+        return Err(parse_error(
+            line_number,
+            "apenas matrizes no formato 'coordinate' são suportadas",
+        ));
+    }
+
+    let mut size: Option<usize> = None;
+    let mut collector = EdgeCollector::new(0);
+    for line in lines {
+        let (line_number, line) = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        let Some(n) = size else {
+            if tokens.len() < 3 {
+                return Err(parse_error(
+                    line_number,
+                    "esperado '<linhas> <colunas> <entradas>'",
+                ));
+            }
+            let rows = parse_index(tokens[0], line_number)?;
+            let cols = parse_index(tokens[1], line_number)?;
+            if rows != cols {
+                return Err(parse_error(
+                    line_number,
+                    &format!("matriz {rows}x{cols} não é quadrada"),
+                ));
+            }
+            size = Some(rows);
+            collector.num_vertices = rows;
+            continue;
+        };
+
+        // Valores numéricos, se houver, são ignorados: qualquer entrada é uma aresta.
+        if tokens.len() < 2 {
+            return Err(parse_error(line_number, "esperado '<i> <j> [valor]'"));
+        }
+        let u = one_based(parse_index(tokens[0], line_number)?, Some(n), line_number)?;
+        let v = one_based(parse_index(tokens[1], line_number)?, Some(n), line_number)?;
+        collector.add(u, v);
+    }
+
+    if size.is_none() {
+        return Err(invalid_data("arquivo Matrix Market sem linha de dimensões"));
+    }
+    Ok(collector.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(content: &str, format: GraphFormat) -> io::Result<ParsedGraph> {
+        parse(content.as_bytes(), format)
+    }
+
+    fn error_message(content: &str, format: GraphFormat) -> String {
+        match parse_str(content, format) {
+            Ok(_) => panic!("esperado erro ao ler {format:?}"),
+            Err(e) => {
+                assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+                e.to_string()
+            }
+        }
+    }
+
+    #[test]
+    fn edge_list_is_zero_based_and_skips_comments_loops_and_duplicates() {
+        let graph = parse_str(
+            "# comentário\n% outro\n\n0 1\n1 2 7.5\n2 1\n3 3\n",
+            GraphFormat::EdgeList,
+        )
+        .unwrap();
+        assert_eq!(graph.num_vertices, 4);
+        assert_eq!(graph.edges, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn edge_list_reports_the_line_of_a_bad_pair() {
+        let message = error_message("0 1\n2\n", GraphFormat::EdgeList);
+        assert_eq!(message, "linha 2: esperado um par de vértices 'u v'");
+        let message = error_message("0 1\n\n1 x\n", GraphFormat::EdgeList);
+        assert_eq!(message, "linha 3: vértice inválido 'x'");
+    }
+
+    #[test]
+    fn one_based_edge_list_shifts_vertices() {
+        let graph = parse_str("1 2\n2 3\n", GraphFormat::EdgeListOneBased).unwrap();
+        assert_eq!(graph.num_vertices, 3);
+        assert_eq!(graph.edges, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn one_based_edge_list_rejects_vertex_zero() {
+        let message = error_message("1 2\n0 2\n", GraphFormat::EdgeListOneBased);
+        assert_eq!(
+            message,
+            "linha 2: vértice 0 em formato indexado a partir de 1"
+        );
+    }
+
+    #[test]
+    fn dimacs_uses_declared_vertex_count() {
+        let graph =
+            parse_str("c exemplo\np edge 5 2\ne 1 2\ne 2 3\n", GraphFormat::Dimacs).unwrap();
+        assert_eq!(graph.num_vertices, 5);
+        assert_eq!(graph.edges, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn dimacs_reports_line_numbered_errors() {
+        assert_eq!(
+            error_message("c\ne 1 2\n", GraphFormat::Dimacs),
+            "linha 2: aresta antes da linha 'p'"
+        );
+        assert_eq!(
+            error_message("p edge 3 1\np edge 3 1\n", GraphFormat::Dimacs),
+            "linha 2: linha 'p' repetida"
+        );
+        assert_eq!(
+            error_message("p edge 3 1\ne 1 4\n", GraphFormat::Dimacs),
+            "linha 2: vértice 4 fora do intervalo 1..=3"
+        );
+        assert_eq!(
+            error_message("p edge 3 1\nx 1 2\n", GraphFormat::Dimacs),
+            "linha 2: tipo de linha DIMACS desconhecido 'x'"
+        );
+        assert_eq!(
+            error_message("c só comentário\n", GraphFormat::Dimacs),
+            "arquivo DIMACS sem linha 'p edge <n> <m>'"
+        );
+    }
+
+    #[test]
+    fn metis_reads_adjacency_lines_and_isolated_vertices() {
+        let graph = parse_str("% comentário\n4 2\n2\n1 3\n2\n\n", GraphFormat::Metis).unwrap();
+        assert_eq!(graph.num_vertices, 4);
+        assert_eq!(graph.edges, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn metis_skips_vertex_and_edge_weights() {
+        // fmt 011: um peso por vértice e um peso por aresta.
+        let graph = parse_str("3 2 011\n5 2 9\n7 1 9 3 4\n1 2 4\n", GraphFormat::Metis).unwrap();
+        assert_eq!(graph.num_vertices, 3);
+        assert_eq!(graph.edges, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn metis_reports_line_numbered_errors() {
+        assert_eq!(
+            error_message("3\n", GraphFormat::Metis),
+            "linha 1: esperado cabeçalho '<n> <m> [fmt]'"
+        );
+        assert_eq!(
+            error_message("2 1 2\n2\n1\n", GraphFormat::Metis),
+            "linha 1: fmt METIS inválido '002'"
+        );
+        assert_eq!(
+            error_message("2 1\n3\n", GraphFormat::Metis),
+            "linha 2: vértice 3 fora do intervalo 1..=2"
+        );
+        assert_eq!(
+            error_message("1 0\n\n2\n", GraphFormat::Metis),
+            "linha 3: mais linhas de adjacência que os 1 vértices declarados"
+        );
+        assert_eq!(
+            error_message("3 1\n2\n1", GraphFormat::Metis),
+            "esperadas 3 linhas de adjacência, encontradas 2"
+        );
+    }
+
+    #[test]
+    fn matrix_market_ignores_values_and_diagonal() {
+        let graph = parse_str(
+            "%%MatrixMarket matrix coordinate real symmetric\n% comentário\n3 3 3\n1 1 4.0\n2 1 -1.0\n3 2 -1.0\n",
+            GraphFormat::MatrixMarket,
+        )
+        .unwrap();
+        assert_eq!(graph.num_vertices, 3);
+        assert_eq!(graph.edges, vec![(1, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn matrix_market_reports_line_numbered_errors() {
+        assert_eq!(
+            error_message("3 3 1\n", GraphFormat::MatrixMarket),
+            "linha 1: esperado '%%MatrixMarket matrix ...'"
+        );
+        assert_eq!(
+            error_message(
+                "%%MatrixMarket matrix array real general\n",
+                GraphFormat::MatrixMarket
+            ),
+            "linha 1: apenas matrizes no formato 'coordinate' são suportadas"
+        );
+        assert_eq!(
+            error_message(
+                "%%MatrixMarket matrix coordinate pattern general\n2 3 1\n",
+                GraphFormat::MatrixMarket
+            ),
+            "linha 2: matriz 2x3 não é quadrada"
+        );
+        assert_eq!(
+            error_message(
+                "%%MatrixMarket matrix coordinate pattern general\n2 2 1\n1 3\n",
+                GraphFormat::MatrixMarket
+            ),
+            "linha 3: vértice 3 fora do intervalo 1..=2"
+        );
+        assert_eq!(
+            error_message("", GraphFormat::MatrixMarket),
+            "linha 1: arquivo Matrix Market vazio"
+        );
+    }
+
+    #[test]
+    fn detection_prefers_header_over_extension() {
+        use std::path::Path;
+
+        let path = Path::new("grafo.txt");
+        assert_eq!(
+            GraphFormat::detect(path, "%%MatrixMarket matrix coordinate pattern general"),
+            GraphFormat::MatrixMarket
+        );
+        assert_eq!(GraphFormat::detect(path, "p edge 3 2"), GraphFormat::Dimacs);
+        assert_eq!(GraphFormat::detect(path, "0 1"), GraphFormat::EdgeList);
+        assert_eq!(
+            GraphFormat::detect(Path::new("grafo.graph"), "3 2"),
+            GraphFormat::Metis
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::{BTreeSet, HashSet};
+
+use kambo_hive::common::GraphModel;
+use rand::{rngs::StdRng, seq::index, Rng, SeedableRng};
+
+use super::Graph;
+
+/// Gera o grafo do modelo com a semente dada; a mesma semente sempre gera o mesmo grafo.
+#[must_use]
+pub fn generate(model: &GraphModel, seed: u64) -> Graph {
+    match *model {
+        GraphModel::ErdosRenyi { n, p } => erdos_renyi(n, p, seed),
+        GraphModel::RandomGeometric { n, radius } => random_geometric(n, radius, seed),
+        GraphModel::BarabasiAlbert { n, m } => barabasi_albert(n, m, seed),
+        GraphModel::Grid { rows, cols } => grid(rows, cols),
+        GraphModel::Tree { n } => random_tree(n, seed),
+        GraphModel::PlantedCliques {
+            n,
+            p,
+            cliques,
+            clique_size,
+        } => planted_cliques(n, p, cliques, clique_size, seed),
+    }
+}
+
+fn random_edges(n: usize, p: f64, rng: &mut StdRng) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for u in 0..n {
+        for v in u + 1..n {
+            if rng.random_bool(p) {
+                edges.push((u, v));
+            }
+        }
+    }
+    edges
+}
+
+#[must_use]
+pub fn erdos_renyi(n: usize, p: f64, seed: u64) -> Graph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    Graph::new(n, &random_edges(n, p, &mut rng))
+}
+
+#[must_use]
+pub fn random_geometric(n: usize, radius: f64, seed: u64) -> Graph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let points: Vec<(f64, f64)> = (0..n)
+        .map(|_| (rng.random::<f64>(), rng.random::<f64>()))
+        .collect();
+    let mut edges = Vec::new();
+    for u in 0..n {
+        for v in u + 1..n {
+            let (dx, dy) = (points[u].0 - points[v].0, points[u].1 - points[v].1);
+            if dx.hypot(dy) <= radius {
+                edges.push((u, v));
+            }
+        }
+    }
+    Graph::new(n, &edges)
+}
+
+/// Começa com uma clique de `m` vértices; cada vértice novo escolhe `m` vizinhos
+/// distintos com probabilidade proporcional ao grau.
+#[must_use]
+pub fn barabasi_albert(n: usize, m: usize, seed: u64) -> Graph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut edges = Vec::new();
+    for u in 0..m.min(n) {
+        for v in u + 1..m.min(n) {
+            edges.push((u, v));
+        }
+    }
+    // Cada vértice aparece uma vez por aresta incidente; os vértices da clique
+    // inicial também aparecem uma vez para que uma clique de um vértice possa ser escolhida.
+    let mut endpoints: Vec<usize> = (0..m.min(n)).collect();
+    endpoints.extend(edges.iter().flat_map(|&edge| <[usize; 2]>::from(edge)));
+    for vertex in m..n {
+        let mut targets = HashSet::new();
+        while targets.len() < m {
+            targets.insert(endpoints[rng.random_range(0..endpoints.len())]);
+        }
+        let mut targets: Vec<usize> = targets.into_iter().collect();
+        targets.sort_unstable();
+        for target in targets {
+            edges.push((target, vertex));
+            endpoints.push(target);
+            endpoints.push(vertex);
+        }
+    }
+    Graph::new(n, &edges)
+}
+
+#[must_use]
+pub fn grid(rows: usize, cols: usize) -> Graph {
+    let id = |row: usize, col: usize| row * cols + col;
+    let mut edges = Vec::new();
+    for row in 0..rows {
+        for col in 0..cols {
+            if col + 1 < cols {
+                edges.push((id(row, col), id(row, col + 1)));
+            }
+            if row + 1 < rows {
+                edges.push((id(row, col), id(row + 1, col)));
+            }
+        }
+    }
+    Graph::new(rows * cols, &edges)
+}
+
+/// Árvore sorteada uniformemente entre as `n^(n-2)` árvores rotuladas, decodificando
+/// uma sequência de Prüfer aleatória.
+#[must_use]
+pub fn random_tree(n: usize, seed: u64) -> Graph {
+    if n < 2 {
+        return Graph::new(n, &[]);
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let prufer: Vec<usize> = (0..n - 2).map(|_| rng.random_range(0..n)).collect();
+    let mut degree = vec![1; n];
+    for &vertex in &prufer {
+        degree[vertex] += 1;
+    }
+    let mut edges = Vec::with_capacity(n - 1);
+    let mut leaves: BTreeSet<usize> = (0..n).filter(|&v| degree[v] == 1).collect();
+    for &vertex in &prufer {
+        let leaf = leaves.pop_first().unwrap();
+        edges.push((leaf, vertex));
+        degree[vertex] -= 1;
+        if degree[vertex] == 1 {
+            leaves.insert(vertex);
+        }
+    }
+    let u = leaves.pop_first().unwrap();
+    let v = leaves.pop_first().unwrap();
+    edges.push((u, v));
+    Graph::new(n, &edges)
+}
+
+/// G(n, p) com `cliques` cliques disjuntas de `clique_size` vértices sorteados.
+#[must_use]
+pub fn planted_cliques(n: usize, p: f64, cliques: usize, clique_size: usize, seed: u64) -> Graph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut edges: HashSet<(usize, usize)> = random_edges(n, p, &mut rng).into_iter().collect();
+    let chosen = index::sample(&mut rng, n, cliques * clique_size).into_vec();
+    for clique in chosen.chunks(clique_size.max(1)) {
+        for (i, &u) in clique.iter().enumerate() {
+            for &v in &clique[i + 1..] {
+                edges.insert((u.min(v), u.max(v)));
+            }
+        }
+    }
+    let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    edges.sort_unstable();
+    Graph::new(n, &edges)
+}
@@ -0,0 +1,375 @@
+use std::{collections::VecDeque, fmt};
+
+use super::Graph;
+
+/// Quanto cada regra reduziu o grafo.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReductionStats {
+    pub original_vertices: usize,
+    pub original_edges: usize,
+    pub reduced_vertices: usize,
+    pub reduced_edges: usize,
+    /// Vértices isolados, fixados com rótulo 1.
+    pub isolated: usize,
+    /// Componentes estrela (incluindo arestas isoladas), com o centro fixado em 2.
+    pub stars: usize,
+    /// Caminhos pendentes `u - v - w` removidos com rótulos 0, 2, 0.
+    pub pendant_paths: usize,
+    /// Folhas removidas de vértices de suporte com mais de duas folhas.
+    pub extra_leaves: usize,
+}
+
+impl fmt::Display for ReductionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let removed = self.original_vertices - self.reduced_vertices;
+        #[allow(clippy::cast_precision_loss)]
+        let percent = if self.original_vertices == 0 {
+            0.0
+        } else {
+            removed as f64 * 100.0 / self.original_vertices as f64
+        };
+        write!(
+            f,
+            "{} -> {} vértices ({percent:.1}% removidos), {} -> {} arestas; {} isolados, {} estrelas, {} caminhos pendentes, {} folhas extras",
+            self.original_vertices,
+            self.reduced_vertices,
+            self.original_edges,
+            self.reduced_edges,
+            self.isolated,
+            self.stars,
+            self.pendant_paths,
+            self.extra_leaves
+        )
+    }
+}
+
+/// Kernel de dominação romana clássica.
+///
+/// Guarda o grafo reduzido pelas regras seguras abaixo, aplicadas até não haver
+/// mais mudanças, e o necessário para levar uma solução do grafo reduzido de volta
+/// aos vértices originais:
+///
+/// - vértice isolado recebe 1;
+/// - componente estrela `K1,k` tem o centro com 2 e as folhas com 0;
+/// - caminho pendente `u - v - w - ...` com `u` folha e `v`, `w` de grau 2 recebe
+///   0, 2, 0 em `u`, `v`, `w` (γR(G) = γR(G - {u, v, w}) + 2);
+/// - um vértice de suporte com três ou mais folhas fica com apenas duas, o que não
+///   muda γR, pois sempre há uma solução ótima com rótulo 2 no suporte.
+///
+/// Se `lift` recebe uma solução ótima do grafo reduzido, devolve uma solução ótima
+/// do original, com peso `Graph::roman_weight(reduced) + fixed_weight()`.
+pub struct Kernel {
+    pub graph: Graph,
+    pub stats: ReductionStats,
+    /// Vértice original de cada vértice do grafo reduzido.
+    original_ids: Vec<usize>,
+    /// Rótulos dos vértices removidos pelas regras de isolados, estrelas e caminhos.
+    fixed: Vec<Option<u8>>,
+    /// Suportes que perderam folhas e as folhas que restaram no grafo reduzido.
+    supports: Vec<(usize, Vec<usize>)>,
+    /// Folhas extras removidas; recebem 0, já que o suporte sempre termina com 2.
+    removed_leaves: Vec<usize>,
+}
+
+impl Kernel {
+    #[must_use]
+    pub fn new(graph: &Graph) -> Self {
+        Reducer::new(graph).run()
+    }
+
+    /// Soma dos rótulos fixados pelas reduções.
+    #[must_use]
+    pub fn fixed_weight(&self) -> usize {
+        self.fixed
+            .iter()
+            .flatten()
+            .map(|&label| usize::from(label))
+            .sum()
+    }
+
+    /// Converte uma rotulação do grafo reduzido em uma do grafo original.
+    ///
+    /// Se a rotulação reduzida for viável, o resultado também é. Suportes que
+    /// perderam folhas passam a 2 (e suas folhas mantidas a 0), o que nunca aumenta o peso.
+    #[must_use]
+    pub fn lift(&self, reduced_labels: &[u8]) -> Vec<u8> {
+        assert_eq!(reduced_labels.len(), self.original_ids.len());
+        let mut labels: Vec<u8> = self.fixed.iter().map(|label| label.unwrap_or(0)).collect();
+        for (&original, &label) in self.original_ids.iter().zip(reduced_labels) {
+            labels[original] = label;
+        }
+        for (support, leaves) in &self.supports {
+            if labels[*support] != 2 {
+                labels[*support] = 2;
+                for &leaf in leaves {
+                    labels[leaf] = 0;
+                }
+            }
+        }
+        for &leaf in &self.removed_leaves {
+            labels[leaf] = 0;
+        }
+        labels
+    }
+}
+
+struct Reducer<'a> {
+    graph: &'a Graph,
+    alive: Vec<bool>,
+    degree: Vec<usize>,
+    fixed: Vec<Option<u8>>,
+    trimmed_supports: Vec<usize>,
+    removed_leaves: Vec<usize>,
+    queue: VecDeque<usize>,
+    stats: ReductionStats,
+}
+
+impl<'a> Reducer<'a> {
+    fn new(graph: &'a Graph) -> Self {
+        let n = graph.get_num_vertices();
+        let degree: Vec<usize> = (0..n).map(|v| graph.get_vertex_degree(v)).collect();
+        let stats = ReductionStats {
+            original_vertices: n,
+            original_edges: degree.iter().sum::<usize>() / 2,
+            ..ReductionStats::default()
+        };
+        Self {
+            graph,
+            alive: vec![true; n],
+            degree,
+            fixed: vec![None; n],
+            trimmed_supports: Vec::new(),
+            removed_leaves: Vec::new(),
+            queue: (0..n).collect(),
+            stats,
+        }
+    }
+
+    fn alive_neighbors(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        self.graph
+            .get_neighbors(vertex)
+            .iter()
+            .copied()
+            .filter(|&neighbor| self.alive[neighbor])
+    }
+
+    /// Remove `vertex` e coloca os vizinhos, cujo grau mudou, na fila.
+    fn remove(&mut self, vertex: usize) {
+        self.alive[vertex] = false;
+        let neighbors: Vec<usize> = self.alive_neighbors(vertex).collect();
+        for neighbor in neighbors {
+            self.degree[neighbor] -= 1;
+            self.queue.push_back(neighbor);
+        }
+    }
+
+    fn fix(&mut self, vertex: usize, label: u8) {
+        self.fixed[vertex] = Some(label);
+        self.remove(vertex);
+    }
+
+    fn run(mut self) -> Kernel {
+        while let Some(vertex) = self.queue.pop_front() {
+            if !self.alive[vertex] {
+                continue;
+            }
+            match self.degree[vertex] {
+                0 => {
+                    self.fix(vertex, 1);
+                    self.stats.isolated += 1;
+                }
+                1 => self.reduce_leaf(vertex),
+                _ => self.reduce_around(vertex),
+            }
+        }
+        self.finish()
+    }
+
+    /// Um vértice só volta para a fila quando seu grau muda, então as regras
+    /// também são tentadas a partir do suporte e do meio de um caminho pendente.
+    fn reduce_around(&mut self, vertex: usize) {
+        let leaf = self
+            .alive_neighbors(vertex)
+            .find(|&v| self.degree[v] == 1)
+            .or_else(|| {
+                (self.degree[vertex] == 2)
+                    .then(|| {
+                        self.alive_neighbors(vertex)
+                            .filter(|&v| self.degree[v] == 2)
+                            .find_map(|v| self.alive_neighbors(v).find(|&u| self.degree[u] == 1))
+                    })
+                    .flatten()
+            });
+        if let Some(leaf) = leaf {
+            self.reduce_leaf(leaf);
+        }
+    }
+
+    fn reduce_leaf(&mut self, leaf: usize) {
+        let support = self.alive_neighbors(leaf).next().unwrap();
+        let support_neighbors: Vec<usize> = self.alive_neighbors(support).collect();
+
+        if support_neighbors.iter().all(|&v| self.degree[v] == 1) {
+            for neighbor in support_neighbors {
+                self.fix(neighbor, 0);
+            }
+            self.fix(support, 2);
+            self.stats.stars += 1;
+        } else if self.degree[support] == 2 {
+            let next = support_neighbors.into_iter().find(|&v| v != leaf).unwrap();
+            if self.degree[next] == 2 {
+                self.fix(leaf, 0);
+                self.fix(next, 0);
+                self.fix(support, 2);
+                self.stats.pendant_paths += 1;
+            }
+        } else if self.degree[support] > 2 {
+            self.trim_leaves(support);
+        }
+    }
+
+    fn trim_leaves(&mut self, support: usize) {
+        let mut leaves: Vec<usize> = self
+            .alive_neighbors(support)
+            .filter(|&v| self.degree[v] == 1)
+            .collect();
+        if leaves.len() <= 2 {
+            return;
+        }
+        for leaf in leaves.split_off(2) {
+            self.remove(leaf);
+            self.removed_leaves.push(leaf);
+            self.stats.extra_leaves += 1;
+        }
+        self.trimmed_supports.push(support);
+    }
+
+    fn finish(mut self) -> Kernel {
+        let original_ids: Vec<usize> = (0..self.alive.len()).filter(|&v| self.alive[v]).collect();
+        let mut reduced_id = vec![usize::MAX; self.alive.len()];
+        for (new, &old) in original_ids.iter().enumerate() {
+            reduced_id[old] = new;
+        }
+        let edges: Vec<(usize, usize)> = original_ids
+            .iter()
+            .flat_map(|&u| {
+                self.alive_neighbors(u)
+                    .filter(move |&v| u < v)
+                    .map(move |v| (u, v))
+            })
+            .map(|(u, v)| (reduced_id[u], reduced_id[v]))
+            .collect();
+
+        // Um suporte que continua no grafo reduzido mantém pelo menos duas folhas;
+        // os que foram removidos por outra regra já estão fixados com 2.
+        self.trimmed_supports.sort_unstable();
+        self.trimmed_supports.dedup();
+        let supports = self
+            .trimmed_supports
+            .iter()
+            .filter(|&&support| self.alive[support])
+            .map(|&support| {
+                let leaves = self
+                    .alive_neighbors(support)
+                    .filter(|&v| self.degree[v] == 1)
+                    .collect();
+                (support, leaves)
+            })
+            .collect();
+
+        self.stats.reduced_vertices = original_ids.len();
+        self.stats.reduced_edges = edges.len();
+        Kernel {
+            graph: Graph::new(original_ids.len(), &edges),
+            stats: self.stats,
+            original_ids,
+            fixed: self.fixed,
+            supports,
+            removed_leaves: self.removed_leaves,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{exact, graph::generators};
+
+    fn optimum(graph: &Graph) -> exact::ExactResult {
+        let result = exact::solve(graph, Duration::from_secs(10));
+        assert!(result.optimal);
+        result
+    }
+
+    /// Confere que γR(G) = γR(kernel) + peso fixado e que `lift` leva soluções
+    /// viáveis do kernel (a ótima, a trivial com tudo 1 e a de `h1`) a soluções
+    /// viáveis de `graph`.
+    fn assert_kernel_is_exact(graph: &Graph) -> Kernel {
+        let kernel = Kernel::new(graph);
+        let expected = optimum(graph).weight;
+        let reduced = optimum(&kernel.graph);
+        assert_eq!(reduced.weight + kernel.fixed_weight(), expected);
+
+        let lifted = kernel.lift(&reduced.solution);
+        assert!(graph.is_roman_dominating(&lifted));
+        assert_eq!(Graph::roman_weight(&lifted), expected);
+
+        let n = kernel.graph.get_num_vertices();
+        for labels in [vec![1; n], kernel.graph.h1()] {
+            let lifted = kernel.lift(&labels);
+            assert!(graph.is_roman_dominating(&lifted));
+            assert!(
+                Graph::roman_weight(&lifted)
+                    <= Graph::roman_weight(&labels) + kernel.fixed_weight()
+            );
+        }
+        kernel
+    }
+
+    #[test]
+    fn isolated_vertices_and_stars_are_fixed() {
+        // Três vértices isolados, uma aresta e a estrela K_1,4.
+        let graph = Graph::new(10, &[(3, 4), (5, 6), (5, 7), (5, 8), (5, 9)]);
+        let kernel = assert_kernel_is_exact(&graph);
+        assert_eq!(kernel.stats.isolated, 3);
+        assert_eq!(kernel.stats.stars, 2);
+        assert_eq!(kernel.stats.reduced_vertices, 0);
+        assert_eq!(kernel.fixed_weight(), 3 + 2 + 2);
+    }
+
+    #[test]
+    fn pendant_paths_are_removed() {
+        // C_5 com a cauda 0 - 5 - 6 - 7.
+        let mut edges: Vec<(usize, usize)> = (0..5).map(|v| (v, (v + 1) % 5)).collect();
+        edges.extend([(0, 5), (5, 6), (6, 7)]);
+        let graph = Graph::new(8, &edges);
+        let kernel = assert_kernel_is_exact(&graph);
+        assert!(kernel.stats.pendant_paths >= 1);
+        assert!(kernel.stats.reduced_vertices < 8);
+    }
+
+    #[test]
+    fn extra_leaves_are_trimmed() {
+        // Triângulo 0 - 1 - 2 com quatro folhas penduradas em 0.
+        let graph = Graph::new(7, &[(0, 1), (1, 2), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6)]);
+        let kernel = assert_kernel_is_exact(&graph);
+        assert_eq!(kernel.stats.extra_leaves, 2);
+        assert_eq!(kernel.stats.reduced_vertices, 5);
+
+        // O suporte passa a 2 mesmo que a solução do kernel não o rotule assim.
+        let lifted = kernel.lift(&vec![1; kernel.graph.get_num_vertices()]);
+        assert_eq!(lifted[0], 2);
+        assert!(lifted[3..].iter().all(|&label| label == 0));
+    }
+
+    #[test]
+    fn random_graphs_keep_the_optimum() {
+        for seed in 0..20 {
+            assert_kernel_is_exact(&generators::random_tree(14, seed));
+            assert_kernel_is_exact(&generators::erdos_renyi(14, 0.12, seed));
+            assert_kernel_is_exact(&generators::erdos_renyi(12, 0.25, seed));
+        }
+    }
+}
@@ -4,4 +4,8 @@
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::cast_possible_truncation)]
+pub mod exact;
+pub mod ga;
 pub mod graph;
+pub mod local_search;
+pub mod variant;
@@ -0,0 +1,316 @@
+use crate::graph::Graph;
+
+/// Critério de aceitação dos movimentos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Aplica o primeiro movimento de melhora encontrado em cada varredura.
+    FirstImprovement,
+    /// Aplica, a cada iteração, o movimento de maior ganho.
+    BestImprovement,
+}
+
+/// Movimentos sobre um vértice `v`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Move {
+    /// `v` com rótulo 1 ou 2 passa a `to`; vizinhos que dependiam só de `v` passam a 1.
+    Demote { vertex: usize, to: u8 },
+    /// `v` passa a 2 e os vizinhos com rótulo 1 passam a 0 (troca de vários 1 por um 2).
+    Promote { vertex: usize },
+}
+
+/// Busca local para dominação romana sobre uma rotulação viável.
+///
+/// Mantém, para cada vértice, quantos vizinhos têm rótulo 2, de modo que o ganho
+/// e a viabilidade de cada movimento são avaliados em O(grau).
+pub struct LocalSearch<'a> {
+    graph: &'a Graph,
+    labels: Vec<u8>,
+    twos_around: Vec<usize>,
+    weight: usize,
+}
+
+impl<'a> LocalSearch<'a> {
+    /// Vértices com rótulo 0 não dominados passam a 1, então a busca sempre parte
+    /// de uma solução viável.
+    #[must_use]
+    pub fn new(graph: &'a Graph, mut labels: Vec<u8>) -> Self {
+        assert_eq!(labels.len(), graph.get_num_vertices());
+        let mut twos_around = vec![0; labels.len()];
+        for (vertex, &label) in labels.iter().enumerate() {
+            if label == 2 {
+                for &neighbor in graph.get_neighbors(vertex) {
+                    twos_around[neighbor] += 1;
+                }
+            }
+        }
+        for (label, &twos) in labels.iter_mut().zip(&twos_around) {
+            if *label == 0 && twos == 0 {
+                *label = 1;
+            }
+        }
+        let weight = Graph::roman_weight(&labels);
+        Self {
+            graph,
+            labels,
+            twos_around,
+            weight,
+        }
+    }
+
+    #[must_use]
+    pub const fn weight(&self) -> usize {
+        self.weight
+    }
+
+    #[must_use]
+    pub fn labels(&self) -> &[u8] {
+        &self.labels
+    }
+
+    #[must_use]
+    pub fn into_labels(self) -> Vec<u8> {
+        self.labels
+    }
+
+    /// Executa até um ótimo local ou até `max_moves` movimentos; retorna quantos foram aplicados.
+    pub fn run(&mut self, strategy: Strategy, max_moves: usize) -> usize {
+        let mut applied = 0;
+        while applied < max_moves {
+            let improved = match strategy {
+                Strategy::FirstImprovement => self.first_improvement_pass(max_moves - applied),
+                Strategy::BestImprovement => usize::from(self.best_improvement_step()),
+            };
+            if improved == 0 {
+                break;
+            }
+            applied += improved;
+        }
+        applied
+    }
+
+    fn first_improvement_pass(&mut self, budget: usize) -> usize {
+        let mut applied = 0;
+        for vertex in 0..self.labels.len() {
+            if applied == budget {
+                break;
+            }
+            if let Some((gain, mv)) = self.best_move_at(vertex) {
+                if gain > 0 {
+                    self.apply(mv);
+                    applied += 1;
+                }
+            }
+        }
+        applied
+    }
+
+    fn best_improvement_step(&mut self) -> bool {
+        let best = (0..self.labels.len())
+            .filter_map(|vertex| self.best_move_at(vertex))
+            .max_by_key(|&(gain, _)| gain);
+        match best {
+            Some((gain, mv)) if gain > 0 => {
+                self.apply(mv);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Dependentes de `vertex`: vizinhos com rótulo 0 cujo único vizinho com rótulo 2 é `vertex`.
+    fn private_neighbors(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        self.graph
+            .get_neighbors(vertex)
+            .iter()
+            .copied()
+            .filter(|&neighbor| self.labels[neighbor] == 0 && self.twos_around[neighbor] == 1)
+    }
+
+    /// Melhor movimento em `vertex` e seu ganho (redução do peso).
+    fn best_move_at(&self, vertex: usize) -> Option<(isize, Move)> {
+        let label = self.labels[vertex];
+        let dominated = self.twos_around[vertex] > 0;
+
+        let demote = match label {
+            1 if dominated => Some((1, Move::Demote { vertex, to: 0 })),
+            2 => {
+                let private = self.private_neighbors(vertex).count().cast_signed();
+                let to = u8::from(!dominated);
+                Some((2 - isize::from(to) - private, Move::Demote { vertex, to }))
+            }
+            _ => None,
+        };
+
+        let promote = (label < 2).then(|| {
+            let ones = self
+                .graph
+                .get_neighbors(vertex)
+                .iter()
+                .filter(|&&neighbor| self.labels[neighbor] == 1)
+                .count()
+                .cast_signed();
+            (ones - isize::from(2 - label), Move::Promote { vertex })
+        });
+
+        match (demote, promote) {
+            (Some(d), Some(p)) => Some(if p.0 > d.0 { p } else { d }),
+            (d, p) => d.or(p),
+        }
+    }
+
+    fn set_label(&mut self, vertex: usize, label: u8) {
+        let old = self.labels[vertex];
+        if old == label {
+            return;
+        }
+        if old == 2 {
+            for &neighbor in self.graph.get_neighbors(vertex) {
+                self.twos_around[neighbor] -= 1;
+            }
+        }
+        if label == 2 {
+            for &neighbor in self.graph.get_neighbors(vertex) {
+                self.twos_around[neighbor] += 1;
+            }
+        }
+        self.weight = self.weight + usize::from(label) - usize::from(old);
+        self.labels[vertex] = label;
+    }
+
+    fn apply(&mut self, mv: Move) {
+        match mv {
+            Move::Demote { vertex, to } => {
+                let private: Vec<usize> = if self.labels[vertex] == 2 {
+                    self.private_neighbors(vertex).collect()
+                } else {
+                    Vec::new()
+                };
+                self.set_label(vertex, to);
+                for neighbor in private {
+                    self.set_label(neighbor, 1);
+                }
+            }
+            Move::Promote { vertex } => {
+                self.set_label(vertex, 2);
+                let ones: Vec<usize> = self
+                    .graph
+                    .get_neighbors(vertex)
+                    .iter()
+                    .copied()
+                    .filter(|&neighbor| self.labels[neighbor] == 1)
+                    .collect();
+                for neighbor in ones {
+                    self.set_label(neighbor, 0);
+                }
+            }
+        }
+    }
+}
+
+/// Aplica a busca local até um ótimo local e devolve a rotulação melhorada.
+#[must_use]
+pub fn improve(graph: &Graph, labels: Vec<u8>, strategy: Strategy) -> Vec<u8> {
+    let mut search = LocalSearch::new(graph, labels);
+    search.run(strategy, usize::MAX);
+    search.into_labels()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::graph::generators;
+
+    const STRATEGIES: [Strategy; 2] = [Strategy::FirstImprovement, Strategy::BestImprovement];
+
+    /// Roda a busca a partir de `labels` e confere que o resultado é viável, não
+    /// custa mais que a rotulação de partida e que os contadores batem com os rótulos.
+    fn assert_improves(graph: &Graph, labels: &[u8], strategy: Strategy) -> Vec<u8> {
+        let mut search = LocalSearch::new(graph, labels.to_vec());
+        let start = search.weight();
+        assert!(graph.is_roman_dominating(search.labels()));
+        search.run(strategy, usize::MAX);
+
+        assert!(graph.is_roman_dominating(search.labels()));
+        assert!(search.weight() <= start);
+        assert_eq!(search.weight(), Graph::roman_weight(search.labels()));
+        let expected = LocalSearch::new(graph, search.labels().to_vec());
+        assert_eq!(search.twos_around, expected.twos_around);
+        search.into_labels()
+    }
+
+    #[test]
+    fn demote_removes_redundant_twos_and_ones() {
+        let path = Graph::new(3, &[(0, 1), (1, 2)]);
+        let search = LocalSearch::new(&path, vec![2, 2, 2]);
+        assert_eq!(
+            search.best_move_at(0),
+            Some((2, Move::Demote { vertex: 0, to: 0 }))
+        );
+        let search = LocalSearch::new(&path, vec![1, 2, 1]);
+        assert_eq!(
+            search.best_move_at(0),
+            Some((1, Move::Demote { vertex: 0, to: 0 }))
+        );
+
+        for strategy in STRATEGIES {
+            // A primeira melhora pode parar no ótimo local 1, 0, 2.
+            let labels = assert_improves(&path, &[2, 2, 2], strategy);
+            assert!(Graph::roman_weight(&labels) <= 3);
+            assert_eq!(assert_improves(&path, &[1, 2, 1], strategy), vec![0, 2, 0]);
+        }
+    }
+
+    #[test]
+    fn promote_replaces_ones_with_a_two() {
+        let star = Graph::new(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        let search = LocalSearch::new(&star, vec![1; 5]);
+        assert_eq!(
+            search.best_move_at(0),
+            Some((3, Move::Promote { vertex: 0 }))
+        );
+
+        for strategy in STRATEGIES {
+            assert_eq!(
+                assert_improves(&star, &[1; 5], strategy),
+                vec![2, 0, 0, 0, 0]
+            );
+        }
+    }
+
+    #[test]
+    fn undominated_zeros_are_repaired() {
+        let path = Graph::new(4, &[(0, 1), (1, 2), (2, 3)]);
+        let search = LocalSearch::new(&path, vec![0, 0, 2, 0]);
+        assert_eq!(search.labels(), [1, 0, 2, 0]);
+        for strategy in STRATEGIES {
+            assert_improves(&path, &[0; 4], strategy);
+        }
+    }
+
+    #[test]
+    fn random_labelings_stay_feasible_and_never_get_worse() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for seed in 0..20 {
+            let graph = generators::erdos_renyi(30, 0.1, seed);
+            let random: Vec<u8> = (0..30).map(|_| rng.random_range(0..=2)).collect();
+            for labels in [random, vec![2; 30], graph.h1()] {
+                for strategy in STRATEGIES {
+                    assert_improves(&graph, &labels, strategy);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn run_stops_at_max_moves() {
+        let graph = generators::erdos_renyi(30, 0.1, 3);
+        for strategy in STRATEGIES {
+            let mut search = LocalSearch::new(&graph, vec![2; 30]);
+            assert_eq!(search.run(strategy, 1), 1);
+            assert!(graph.is_roman_dominating(search.labels()));
+            assert!(search.weight() < 60);
+        }
+    }
+}
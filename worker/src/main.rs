@@ -1,96 +1,593 @@
-use std::{env, path::Path, sync::Arc, time::Instant};
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use kambo_hive::{
-    common::{GARunner, Task, TaskResult},
+    common::{
+        EvaluationJob, FitnessEvaluator, GAConfig, GARunner, GraphFormat, HostChannel, Problem,
+        SyntheticGraph, Task, TaskResult,
+    },
     utils::{discover_host, init_logger},
-    worker::client::start_worker,
+    worker::{client::start_worker, external::ExternalProcessRunner, registry::RunnerRegistry},
+};
+use kambo_hive_worker::{
+    exact,
+    ga::{GeneticAlgorithm, Migration},
+    graph::{generators, CsrGraph, Graph, Kernel},
+    local_search::{self, Strategy},
+    variant::{self, RomanVariant},
 };
-use kambo_hive_worker::graph::Graph;
 use log::{error, info};
 use rand::{rng, Rng};
-use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct GAConfig {
-    pub max_stagnant: usize,
-    pub generations: usize,
-    pub tournament_size: usize,
-    pub crossover_probability: f32,
-    pub pop_size: Option<usize>,
+/// Grafo da task, a variante do problema e, se `GAConfig::reduce` estiver
+/// ativo, o kernel sobre o qual os algoritmos realmente executam.
+struct Instance {
+    graph: Graph,
+    variant: Box<dyn RomanVariant>,
+    kernel: Option<Kernel>,
+}
+
+/// Lê o grafo `graph_id` de `graphs_path`, no formato `format` ou no detectado, ou,
+/// se ele for sintético, o gera.
+/// Devolve também o caminho do arquivo, onde ficam os arquivos auxiliares.
+fn load_graph(
+    graphs_path: &str,
+    graph_id: &str,
+    synthetic: Option<&SyntheticGraph>,
+    format: Option<GraphFormat>,
+) -> Result<(Graph, PathBuf), String> {
+    let graph_file_path = Path::new(graphs_path).join(graph_id);
+
+    let graph = if let Some(synthetic) = synthetic {
+        info!(
+            "Gerando grafo sintético '{}' ({:?}, semente {})",
+            graph_id, synthetic.model, synthetic.seed
+        );
+        generators::generate(&synthetic.model, synthetic.seed)
+    } else {
+        info!("Carregando grafo de: {}", graph_file_path.display());
+        Graph::load(graph_file_path.to_str().unwrap(), format).map_err(|e| {
+            format!(
+                "Falha ao carregar o arquivo do grafo '{}': {e}",
+                graph_file_path.display()
+            )
+        })?
+    };
+    Ok((graph, graph_file_path))
+}
+
+impl Instance {
+    fn load(graphs_path: &str, task: &Task, config: &GAConfig) -> Result<Self, String> {
+        let (graph, graph_file_path) = load_graph(
+            graphs_path,
+            &task.graph_id,
+            task.synthetic.as_ref(),
+            task.graph_format,
+        )?;
+        let variant = variant::load(task.problem, &graph, &graph_file_path)?;
+
+        let kernel = if config.reduce {
+            let kernel = Kernel::new(&graph);
+            info!("Redução do grafo '{}': {}", task.graph_id, kernel.stats);
+            Some(kernel)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            graph,
+            variant,
+            kernel,
+        })
+    }
+
+    /// Grafo a ser resolvido: o kernel, se houver, ou o grafo original.
+    fn target(&self) -> &Graph {
+        self.kernel
+            .as_ref()
+            .map_or(&self.graph, |kernel| &kernel.graph)
+    }
+
+    /// Leva uma solução de `target()` para o grafo original.
+    fn lift(&self, labels: Vec<u8>) -> Vec<u8> {
+        match &self.kernel {
+            Some(kernel) => kernel.lift(&labels),
+            None => labels,
+        }
+    }
+
+    fn fixed_weight(&self) -> usize {
+        self.kernel.as_ref().map_or(0, Kernel::fixed_weight)
+    }
+
+    /// Limite inferior barato, conhecido apenas para a dominação romana clássica.
+    fn lower_bound(&self) -> Option<f64> {
+        (self.variant.problem() == Problem::Roman)
+            .then(|| (exact::degree_lower_bound(self.target()) + self.fixed_weight()) as f64)
+    }
+}
+
+/// A redução do grafo e a busca local em cada indivíduo (`GAConfig::local_search`,
+/// verificada só se `local_search_option`) existem apenas para `roman`.
+fn check_roman_options(
+    task: &Task,
+    config: &GAConfig,
+    local_search_option: bool,
+) -> Result<(), String> {
+    if task.problem == Problem::Roman {
+        return Ok(());
+    }
+    if config.reduce {
+        return Err(format!(
+            "A redução do grafo (reduce) só está disponível para o problema '{}', não para '{}'",
+            Problem::Roman,
+            task.problem
+        ));
+    }
+    if local_search_option && config.local_search {
+        return Err(format!(
+            "A busca local (local_search) só está disponível para o problema '{}', não para '{}'",
+            Problem::Roman,
+            task.problem
+        ));
+    }
+    Ok(())
 }
 
 pub struct HeuristicRunner {
     graphs_path: String,
+    heuristic: Option<u8>, // None sorteia uma heurística por task
+    local_search: bool,
 }
 
 impl GARunner for HeuristicRunner {
-    fn run(&self, task: Task, worker_id: Uuid) -> TaskResult {
+    type Config = GAConfig;
+
+    /// A busca local que segue a heurística só existe para `roman`.
+    fn problems(&self) -> Vec<Problem> {
+        if self.local_search {
+            vec![Problem::Roman]
+        } else {
+            Problem::ALL.to_vec()
+        }
+    }
+
+    fn check(&self, task: &Task, config: &GAConfig) -> Result<(), String> {
+        check_roman_options(task, config, false)
+    }
+
+    fn run(&self, task: Task, ga_config: GAConfig, worker_id: Uuid) -> Result<TaskResult, String> {
         info!(
             "Worker {} processando a task {} para o grafo '{}'",
             worker_id, task.id, task.graph_id
         );
 
-        let _ga_config: GAConfig = serde_json::from_str(&task.ag_config)
-            .expect("Falha ao deserializar a GAConfig da task. A configuração é necessária.");
-
         let start_time = Instant::now();
-        let graph_file_path = Path::new(&self.graphs_path)
-            .join(&task.graph_id)
-            .to_str()
-            .unwrap()
-            .to_string();
-
-        info!("Carregando grafo de: {graph_file_path}");
-        let graph =
-            Graph::from_file(&graph_file_path).expect("Falha ao carregar o arquivo do grafo");
+        let instance = Instance::load(&self.graphs_path, &task, &ga_config)?;
+        let graph = &instance.graph;
+        let variant = &instance.variant;
 
-        let heuristic_choice = rng().random_range(1..=4);
+        let heuristic_choice = self.heuristic.unwrap_or_else(|| rng().random_range(1..=4));
+        let csr = CsrGraph::from(instance.target());
         let solution_data = match heuristic_choice {
-            1 => graph.h1(),
-            2 => graph.h2(),
-            3 => graph.h3(),
-            4 => graph.h4(),
+            1 => csr.h1(),
+            2 => csr.h2(),
+            3 => csr.h3(),
+            4 => csr.h4(),
             _ => unreachable!(),
         };
+        let solution_data = variant.lift_roman(graph, instance.lift(solution_data));
 
-        let algorithm_details = format!("H{}", heuristic_choice);
-        let fitness = solution_data.iter().map(|&value| f64::from(value)).sum();
+        let solution_data = if self.local_search {
+            local_search::improve(graph, solution_data, Strategy::FirstImprovement)
+        } else {
+            solution_data
+        };
+
+        let algorithm_details = format!(
+            "H{}{}",
+            heuristic_choice,
+            if self.local_search {
+                " + busca local"
+            } else {
+                ""
+            }
+        );
+        let fitness = variant.cost(&solution_data) as f64;
         let processing_time_ms = start_time.elapsed().as_millis() as u64;
 
         info!(
-            "Task {} finalizada para o grafo '{}' com fitness de {} (usando {})",
-            task.id, task.graph_id, fitness, algorithm_details
+            "Task {} finalizada para o grafo '{}' ({}) com fitness de {} (usando {})",
+            task.id, task.graph_id, task.problem, fitness, algorithm_details
         );
 
-        TaskResult {
+        Ok(TaskResult {
             task_id: task.id,
             graph_id: task.graph_id,
+            algorithm: task.algorithm,
+            configuration: task.configuration,
+            problem: task.problem,
+            seed: task.seed,
             worker_id,
             fitness,
-            solution_data: Vec::new(),
+            solution_data,
             interations_run: graph.get_num_vertices() as u32,
             processing_time_ms,
+            lower_bound: instance.lower_bound(),
+            optimal: false,
+            trace: Vec::new(),
+        })
+    }
+}
+
+pub struct GeneticRunner {
+    graphs_path: String,
+}
+
+impl GeneticRunner {
+    fn execute(
+        &self,
+        task: Task,
+        ga_config: GAConfig,
+        worker_id: Uuid,
+        host: Option<&mut dyn HostChannel>,
+    ) -> Result<TaskResult, String> {
+        info!(
+            "Worker {} executando o AG na task {} para o grafo '{}' ({}, semente {})",
+            worker_id, task.id, task.graph_id, task.problem, task.seed
+        );
+
+        let start_time = Instant::now();
+        let instance = Instance::load(&self.graphs_path, &task, &ga_config)?;
+        let mut ga = GeneticAlgorithm::new(
+            instance.target(),
+            instance.variant.as_ref(),
+            &ga_config,
+            task.seed,
+        );
+        let migration = task.island.as_ref().map(|island| {
+            info!(
+                "Task {} é a ilha {} de {} do grupo {}",
+                task.id, island.island, island.model.islands, island.group_id
+            );
+            Migration {
+                interval: island.model.migration_interval as usize,
+                count: island.model.migrants as usize,
+            }
+        });
+        let result = match host {
+            Some(host) => ga.run_cooperative(host, migration.as_ref()),
+            None => ga.run(),
+        };
+        let solution_data = instance.lift(result.solution);
+        let fitness = instance.variant.cost(&solution_data);
+        // O AG executa sobre o kernel; os rótulos fixados pela redução somam ao custo.
+        let mut trace = result.trace;
+        for point in &mut trace {
+            point.best_fitness += instance.fixed_weight() as f64;
+        }
+        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+
+        info!(
+            "Task {} finalizada para o grafo '{}' com fitness de {} após {} gerações",
+            task.id, task.graph_id, fitness, result.generations_run
+        );
+
+        Ok(TaskResult {
+            task_id: task.id,
+            graph_id: task.graph_id,
+            algorithm: task.algorithm,
+            configuration: task.configuration,
+            problem: task.problem,
+            seed: task.seed,
+            worker_id,
+            fitness: fitness as f64,
+            solution_data,
+            interations_run: result.generations_run as u32,
+            processing_time_ms,
+            lower_bound: instance.lower_bound(),
+            optimal: false,
+            trace,
+        })
+    }
+}
+
+impl GARunner for GeneticRunner {
+    type Config = GAConfig;
+
+    fn check(&self, task: &Task, config: &GAConfig) -> Result<(), String> {
+        check_roman_options(task, config, true)
+    }
+
+    fn run(&self, task: Task, ga_config: GAConfig, worker_id: Uuid) -> Result<TaskResult, String> {
+        self.execute(task, ga_config, worker_id, None)
+    }
+
+    fn run_cooperative(
+        &self,
+        task: Task,
+        ga_config: GAConfig,
+        worker_id: Uuid,
+        host: &mut dyn HostChannel,
+    ) -> Result<TaskResult, String> {
+        self.execute(task, ga_config, worker_id, Some(host))
+    }
+}
+
+/// Nome sob o qual `CostEvaluator` é registrado.
+const COST_EVALUATOR: &str = "cost";
+
+/// Grafo e variante de uma instância avaliada por `CostEvaluator`.
+type EvaluatedInstance = (Graph, Box<dyn RomanVariant>);
+
+/// Avaliador do modo mestre-escravo: o custo de cada rotulação na variante do
+/// problema, rejeitando rotulações inviáveis. Mantém as instâncias já carregadas.
+pub struct CostEvaluator {
+    graphs_path: String,
+    instances: Mutex<HashMap<(String, Problem), Arc<EvaluatedInstance>>>,
+}
+
+impl CostEvaluator {
+    fn instance(&self, job: &EvaluationJob) -> Result<Arc<EvaluatedInstance>, String> {
+        let key = (job.graph_id.clone(), job.problem);
+        if let Some(instance) = self.instances.lock().unwrap().get(&key) {
+            return Ok(Arc::clone(instance));
+        }
+        let (graph, graph_file_path) = load_graph(
+            &self.graphs_path,
+            &job.graph_id,
+            job.synthetic.as_ref(),
+            job.graph_format,
+        )?;
+        let variant = variant::load(job.problem, &graph, &graph_file_path)?;
+        let instance = Arc::new((graph, variant));
+        self.instances
+            .lock()
+            .unwrap()
+            .insert(key, Arc::clone(&instance));
+        Ok(instance)
+    }
+}
+
+impl FitnessEvaluator for CostEvaluator {
+    fn evaluate(&self, job: &EvaluationJob) -> Result<Vec<f64>, String> {
+        let instance = self.instance(job)?;
+        let (graph, variant) = instance.as_ref();
+        job.individuals
+            .iter()
+            .enumerate()
+            .map(|(index, labels)| {
+                if variant.is_feasible(graph, labels) {
+                    Ok(variant.cost(labels) as f64)
+                } else {
+                    Err(format!(
+                        "Indivíduo {} do lote {} é inviável para '{}'",
+                        job.offset + index,
+                        job.batch_id,
+                        job.problem
+                    ))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Tempo limite do solver exato quando `time_limit_secs` não é informado.
+const DEFAULT_EXACT_TIME_LIMIT_SECS: u64 = 60;
+
+pub struct ExactRunner {
+    graphs_path: String,
+}
+
+impl GARunner for ExactRunner {
+    type Config = GAConfig;
+
+    fn problems(&self) -> Vec<Problem> {
+        vec![Problem::Roman]
+    }
+
+    fn run(&self, task: Task, ga_config: GAConfig, worker_id: Uuid) -> Result<TaskResult, String> {
+        info!(
+            "Worker {} executando o solver exato na task {} para o grafo '{}'",
+            worker_id, task.id, task.graph_id
+        );
+
+        let start_time = Instant::now();
+        let instance = Instance::load(&self.graphs_path, &task, &ga_config)?;
+        let time_limit = Duration::from_secs(
+            ga_config
+                .time_limit_secs
+                .unwrap_or(DEFAULT_EXACT_TIME_LIMIT_SECS),
+        );
+        let result = exact::solve(instance.target(), time_limit);
+        let solution_data = instance.lift(result.solution);
+        let weight = Graph::roman_weight(&solution_data);
+        let lower_bound = (result.lower_bound + instance.fixed_weight()).min(weight);
+        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+
+        info!(
+            "Task {} finalizada para o grafo '{}' com fitness de {} (limite inferior {}, ótimo: {}, {} nós)",
+            task.id,
+            task.graph_id,
+            weight,
+            lower_bound,
+            result.optimal,
+            result.nodes_explored
+        );
+
+        Ok(TaskResult {
+            task_id: task.id,
+            graph_id: task.graph_id,
+            algorithm: task.algorithm,
+            configuration: task.configuration,
+            problem: task.problem,
+            seed: task.seed,
+            worker_id,
+            fitness: weight as f64,
+            solution_data,
+            interations_run: u32::try_from(result.nodes_explored).unwrap_or(u32::MAX),
+            processing_time_ms,
+            lower_bound: Some(lower_bound as f64),
+            optimal: result.optimal,
+            trace: Vec::new(),
+        })
+    }
+}
+
+/// Divide o comando de um solver externo em programa e argumentos com as regras do
+/// shell: espaços separam as palavras, aspas simples preservam tudo o que contêm,
+/// aspas duplas preservam os espaços e aceitam `\"`, `\\`, `\$` e `` \` ``, e fora
+/// das aspas a barra invertida escapa o caractere seguinte.
+fn split_command(command: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    // Distingue uma palavra vazia entre aspas ('') da ausência de palavra.
+    let mut in_word = false;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("aspas simples sem fechamento".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("aspas duplas sem fechamento".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("aspas duplas sem fechamento".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some('\n') => {}
+                    Some(c) => word.push(c),
+                    None => return Err("barra invertida no fim do comando".to_string()),
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
         }
     }
+    if in_word {
+        words.push(word);
+    }
+    if words.is_empty() {
+        return Err("comando vazio".to_string());
+    }
+    Ok(words)
+}
+
+#[derive(Default)]
+struct WorkerOptions {
+    auto: bool,
+    positional: Vec<String>,
+    external: Vec<(String, Vec<String>)>, // (nome, programa e argumentos)
+    external_timeout: Option<Duration>,
+    external_memory_mb: Option<u64>,
+}
+
+impl WorkerOptions {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let mut value = |flag: &str| {
+                iter.next()
+                    .cloned()
+                    .ok_or_else(|| format!("A opção '{flag}' exige um valor."))
+            };
+            match arg.as_str() {
+                "--auto" => options.auto = true,
+                "--external" => {
+                    let spec = value(arg)?;
+                    let (name, command) = spec.split_once('=').ok_or_else(|| {
+                        format!("Solver externo inválido '{spec}', use <nome>=<comando>.")
+                    })?;
+                    let command = split_command(command).map_err(|e| {
+                        format!("Comando inválido para o solver externo '{name}': {e}")
+                    })?;
+                    options.external.push((name.to_string(), command));
+                }
+                "--external-timeout" => {
+                    let secs: u64 = value(arg)?
+                        .parse()
+                        .map_err(|e| format!("Tempo limite inválido: {e}"))?;
+                    options.external_timeout = Some(Duration::from_secs(secs));
+                }
+                "--external-memory-mb" => {
+                    let mb = value(arg)?
+                        .parse()
+                        .map_err(|e| format!("Limite de memória inválido: {e}"))?;
+                    options.external_memory_mb = Some(mb);
+                }
+                _ => options.positional.push(arg.clone()),
+            }
+        }
+        Ok(options)
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     init_logger();
     let args: Vec<String> = env::args().collect();
+    let options = match WorkerOptions::parse(&args[1..]) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
 
-    if args.len() < 3 {
-        eprintln!("Uso: {} <host_addr:port> <graphs_path>", args[0]);
-        eprintln!("   ou: {} --auto <graphs_path>", args[0]);
+    if options.positional.len() < if options.auto { 1 } else { 2 } {
+        eprintln!("Uso: {} <host_addr:port> <graphs_path> [opções]", args[0]);
+        eprintln!("   ou: {} --auto <graphs_path> [opções]", args[0]);
         eprintln!("A ordem de '--auto' e '<graphs_path>' não importa.");
+        eprintln!("Opções:");
+        eprintln!("  --external <nome>=<comando>   registra um solver externo sob <nome>;");
+        eprintln!("                                o comando aceita aspas como no shell");
+        eprintln!("  --external-timeout <segundos> tempo limite dos solvers externos");
+        eprintln!("  --external-memory-mb <MB>     limite de memória dos solvers externos");
         std::process::exit(1);
     }
 
     let host_addr: String;
     let graphs_path: String;
 
-    if args.contains(&"--auto".to_string()) {
+    if options.auto {
         info!("Iniciando descoberta automática de host...");
         host_addr = match discover_host() {
             Ok(addr) => {
@@ -103,15 +600,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         };
 
-        graphs_path = args
-            .iter()
-            .skip(1) // Pula o nome do programa
-            .find(|arg| **arg != "--auto")
-            .expect("Caminho para os grafos não foi fornecido junto com a flag --auto.")
-            .clone();
+        graphs_path = options.positional[0].clone();
     } else {
-        host_addr = args[1].clone();
-        graphs_path = args[2].clone();
+        host_addr = options.positional[0].clone();
+        graphs_path = options.positional[1].clone();
     }
 
     let worker_id = Uuid::new_v4();
@@ -120,13 +612,121 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Conectando ao host: {host_addr}");
     info!("Usando grafos de: {graphs_path}");
 
-    let ga_runner = Arc::new(HeuristicRunner {
-        graphs_path: graphs_path.clone(),
-    });
+    let mut registry = RunnerRegistry::new();
+    registry.register(
+        "ga",
+        GeneticRunner {
+            graphs_path: graphs_path.clone(),
+        },
+    );
+    registry.register(
+        "exact",
+        ExactRunner {
+            graphs_path: graphs_path.clone(),
+        },
+    );
+    for local_search in [false, true] {
+        let suffix = if local_search { "-ls" } else { "" };
+        registry.register(
+            &format!("heuristic{suffix}"),
+            HeuristicRunner {
+                graphs_path: graphs_path.clone(),
+                heuristic: None,
+                local_search,
+            },
+        );
+        for heuristic in 1..=4 {
+            registry.register(
+                &format!("h{heuristic}{suffix}"),
+                HeuristicRunner {
+                    graphs_path: graphs_path.clone(),
+                    heuristic: Some(heuristic),
+                    local_search,
+                },
+            );
+        }
+    }
+    for (name, command) in &options.external {
+        let (program, args) = command
+            .split_first()
+            .expect("split_command devolve ao menos uma palavra");
+        let mut runner = ExternalProcessRunner::new(program, args.to_vec(), &graphs_path);
+        runner.timeout = options.external_timeout;
+        runner.memory_limit_bytes = options.external_memory_mb.map(|mb| mb * 1024 * 1024);
+        registry.register(name, runner);
+    }
+    registry.register_evaluator(
+        COST_EVALUATOR,
+        CostEvaluator {
+            graphs_path: graphs_path.clone(),
+            instances: Mutex::new(HashMap::new()),
+        },
+    );
+    info!(
+        "Algoritmos registrados: {:?}, avaliadores: {:?}",
+        registry.algorithms(),
+        registry.evaluators()
+    );
 
-    if let Err(e) = start_worker(&host_addr, worker_id, ga_runner).await {
+    if let Err(e) = start_worker(&host_addr, worker_id, Arc::new(registry)).await {
         error!("Erro fatal no worker: {e}");
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::split_command;
+
+    #[test]
+    fn split_command_separates_on_whitespace() {
+        assert_eq!(
+            split_command("  python3   solver.py\t--fast ").unwrap(),
+            ["python3", "solver.py", "--fast"]
+        );
+    }
+
+    #[test]
+    fn split_command_keeps_quoted_spaces() {
+        assert_eq!(
+            split_command(r#"/opt/meus solvers/ga "arquivo com espaço.json" 'a "b" c'"#).unwrap(),
+            [
+                "/opt/meus",
+                "solvers/ga",
+                "arquivo com espaço.json",
+                r#"a "b" c"#
+            ]
+        );
+        assert_eq!(
+            split_command(r#"'/opt/meus solvers/ga' --nome=" x "''"#).unwrap(),
+            ["/opt/meus solvers/ga", "--nome= x "]
+        );
+        assert_eq!(split_command("ga '' fim").unwrap(), ["ga", "", "fim"]);
+    }
+
+    #[test]
+    fn split_command_handles_backslashes() {
+        assert_eq!(
+            split_command(r#"ga meu\ arquivo "a\"b\\c\d" 'e\f'"#).unwrap(),
+            ["ga", "meu arquivo", r#"a"b\c\d"#, r"e\f"]
+        );
+    }
+
+    #[test]
+    fn split_command_rejects_malformed_commands() {
+        assert_eq!(split_command("   ").unwrap_err(), "comando vazio");
+        assert_eq!(
+            split_command("ga 'sem fim").unwrap_err(),
+            "aspas simples sem fechamento"
+        );
+        assert_eq!(
+            split_command(r#"ga "sem fim"#).unwrap_err(),
+            "aspas duplas sem fechamento"
+        );
+        assert_eq!(
+            split_command(r"ga \").unwrap_err(),
+            "barra invertida no fim do comando"
+        );
+    }
+}
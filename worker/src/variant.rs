@@ -0,0 +1,495 @@
+use std::{fs, path::Path};
+
+use kambo_hive::common::{Problem, VERTEX_WEIGHTS_EXTENSION};
+
+use crate::{ga, graph::Graph};
+
+/// Definição de uma variante de dominação romana: alfabeto de rótulos, regra de
+/// viabilidade e função de custo.
+///
+/// As heurísticas constroem uma rotulação romana clássica e a convertem com
+/// `lift_roman`; o AG usa `repair` para tornar viável qualquer rotulação do alfabeto.
+pub trait RomanVariant: Send + Sync {
+    fn problem(&self) -> Problem;
+
+    /// Os rótulos válidos são `0..=max_label`.
+    fn max_label(&self) -> u8;
+
+    /// Vértices que violam a regra de viabilidade.
+    fn violations(&self, graph: &Graph, labels: &[u8]) -> Vec<usize>;
+
+    /// Torna `labels` viável, removendo rótulos redundantes quando possível.
+    fn repair(&self, graph: &Graph, labels: &mut [u8]);
+
+    fn cost(&self, labels: &[u8]) -> usize {
+        Graph::roman_weight(labels)
+    }
+
+    fn is_feasible(&self, graph: &Graph, labels: &[u8]) -> bool {
+        labels.len() == graph.get_num_vertices()
+            && labels.iter().all(|&label| label <= self.max_label())
+            && self.violations(graph, labels).is_empty()
+    }
+
+    /// Converte uma rotulação romana clássica viável em uma rotulação viável desta variante.
+    fn lift_roman(&self, graph: &Graph, roman: Vec<u8>) -> Vec<u8> {
+        let mut labels = roman;
+        self.repair(graph, &mut labels);
+        labels
+    }
+}
+
+/// Cria a variante de `problem` para o grafo lido de `graph_path`.
+///
+/// `Problem::WeightedRoman` lê os pesos dos vértices de `<graph_path>.weights`
+/// (inteiros não negativos separados por espaços ou quebras de linha).
+pub fn load(
+    problem: Problem,
+    graph: &Graph,
+    graph_path: &Path,
+) -> Result<Box<dyn RomanVariant>, String> {
+    Ok(match problem {
+        Problem::Roman => Box::new(Roman),
+        Problem::DoubleRoman => Box::new(DoubleRoman),
+        Problem::Italian => Box::new(Italian),
+        Problem::TotalRoman => {
+            if let Some(vertex) =
+                (0..graph.get_num_vertices()).find(|&v| graph.get_vertex_degree(v) == 0)
+            {
+                return Err(format!(
+                    "dominação romana total não está definida para grafos com vértices isolados (vértice {vertex})"
+                ));
+            }
+            Box::new(TotalRoman)
+        }
+        Problem::WeightedRoman => {
+            let mut weights_path = graph_path.as_os_str().to_owned();
+            weights_path.push(".");
+            weights_path.push(VERTEX_WEIGHTS_EXTENSION);
+            Box::new(WeightedRoman::from_file(Path::new(&weights_path), graph)?)
+        }
+    })
+}
+
+fn neighbors_with_label_at_least(graph: &Graph, labels: &[u8], vertex: usize, min: u8) -> usize {
+    graph
+        .get_neighbors(vertex)
+        .iter()
+        .filter(|&&neighbor| labels[neighbor] >= min)
+        .count()
+}
+
+/// Dominação romana clássica.
+pub struct Roman;
+
+impl RomanVariant for Roman {
+    fn problem(&self) -> Problem {
+        Problem::Roman
+    }
+
+    fn max_label(&self) -> u8 {
+        2
+    }
+
+    fn violations(&self, graph: &Graph, labels: &[u8]) -> Vec<usize> {
+        graph.undominated_vertices(labels)
+    }
+
+    fn repair(&self, graph: &Graph, labels: &mut [u8]) {
+        ga::repair(graph, labels);
+    }
+
+    fn lift_roman(&self, _graph: &Graph, roman: Vec<u8>) -> Vec<u8> {
+        roman
+    }
+}
+
+/// Dominação romana dupla, com rótulos {0, 1, 2, 3}.
+pub struct DoubleRoman;
+
+impl DoubleRoman {
+    fn is_satisfied(graph: &Graph, labels: &[u8], vertex: usize) -> bool {
+        match labels[vertex] {
+            0 => {
+                neighbors_with_label_at_least(graph, labels, vertex, 3) > 0
+                    || neighbors_with_label_at_least(graph, labels, vertex, 2) >= 2
+            }
+            1 => neighbors_with_label_at_least(graph, labels, vertex, 2) > 0,
+            _ => true,
+        }
+    }
+}
+
+impl RomanVariant for DoubleRoman {
+    fn problem(&self) -> Problem {
+        Problem::DoubleRoman
+    }
+
+    fn max_label(&self) -> u8 {
+        3
+    }
+
+    fn violations(&self, graph: &Graph, labels: &[u8]) -> Vec<usize> {
+        (0..labels.len())
+            .filter(|&vertex| !Self::is_satisfied(graph, labels, vertex))
+            .collect()
+    }
+
+    /// Vértices com rótulo 0 ou 1 recebem, em ordem, o menor rótulo viável dados os
+    /// vizinhos com rótulo 2 ou 3 naquele momento, o que pode levá-los a 2. O conjunto
+    /// de vértices com rótulo 2 ou mais só cresce, então um vértice já satisfeito
+    /// continua satisfeito e o resultado é viável; a ordem, porém, altera o custo.
+    fn repair(&self, graph: &Graph, labels: &mut [u8]) {
+        for vertex in 0..labels.len() {
+            if labels[vertex] >= 2 {
+                continue;
+            }
+            let threes = neighbors_with_label_at_least(graph, labels, vertex, 3);
+            let twos = neighbors_with_label_at_least(graph, labels, vertex, 2);
+            labels[vertex] = if threes > 0 || twos >= 2 {
+                0
+            } else if twos > 0 {
+                1
+            } else {
+                2
+            };
+        }
+    }
+
+    /// Rótulos 1 e 2 passam a 2 e 3, o que preserva a viabilidade.
+    fn lift_roman(&self, graph: &Graph, roman: Vec<u8>) -> Vec<u8> {
+        let mut labels: Vec<u8> = roman
+            .into_iter()
+            .map(|label| if label == 0 { 0 } else { label + 1 })
+            .collect();
+        self.repair(graph, &mut labels);
+        labels
+    }
+}
+
+/// Dominação romana total: além da regra clássica, todo vértice com rótulo
+/// positivo tem um vizinho com rótulo positivo.
+pub struct TotalRoman;
+
+impl RomanVariant for TotalRoman {
+    fn problem(&self) -> Problem {
+        Problem::TotalRoman
+    }
+
+    fn max_label(&self) -> u8 {
+        2
+    }
+
+    fn violations(&self, graph: &Graph, labels: &[u8]) -> Vec<usize> {
+        let mut violations = graph.undominated_vertices(labels);
+        violations.extend((0..labels.len()).filter(|&vertex| {
+            labels[vertex] > 0 && neighbors_with_label_at_least(graph, labels, vertex, 1) == 0
+        }));
+        violations.sort_unstable();
+        violations.dedup();
+        violations
+    }
+
+    /// Aplica o reparo clássico e dá rótulo 1 a um vizinho de cada vértice positivo isolado.
+    fn repair(&self, graph: &Graph, labels: &mut [u8]) {
+        ga::repair(graph, labels);
+        for vertex in 0..labels.len() {
+            if labels[vertex] > 0 && neighbors_with_label_at_least(graph, labels, vertex, 1) == 0 {
+                if let Some(&neighbor) = graph.get_neighbors(vertex).first() {
+                    labels[neighbor] = 1;
+                }
+            }
+        }
+    }
+}
+
+/// Dominação italiana (romana {2}).
+pub struct Italian;
+
+impl Italian {
+    fn neighbor_sums(graph: &Graph, labels: &[u8]) -> Vec<usize> {
+        (0..labels.len())
+            .map(|vertex| {
+                graph
+                    .get_neighbors(vertex)
+                    .iter()
+                    .map(|&neighbor| usize::from(labels[neighbor]))
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+impl RomanVariant for Italian {
+    fn problem(&self) -> Problem {
+        Problem::Italian
+    }
+
+    fn max_label(&self) -> u8 {
+        2
+    }
+
+    fn violations(&self, graph: &Graph, labels: &[u8]) -> Vec<usize> {
+        let sums = Self::neighbor_sums(graph, labels);
+        (0..labels.len())
+            .filter(|&vertex| labels[vertex] == 0 && sums[vertex] < 2)
+            .collect()
+    }
+
+    /// Vértices com rótulo 0 sem cobertura passam a 1; depois, vértices com rótulo 1
+    /// voltam a 0 quando eles e seus vizinhos com rótulo 0 continuam cobertos.
+    fn repair(&self, graph: &Graph, labels: &mut [u8]) {
+        let mut sums = Self::neighbor_sums(graph, labels);
+        for vertex in 0..labels.len() {
+            if labels[vertex] == 0 && sums[vertex] < 2 {
+                labels[vertex] = 1;
+                for &neighbor in graph.get_neighbors(vertex) {
+                    sums[neighbor] += 1;
+                }
+            }
+        }
+        for vertex in 0..labels.len() {
+            let removable = labels[vertex] == 1
+                && sums[vertex] >= 2
+                && graph
+                    .get_neighbors(vertex)
+                    .iter()
+                    .all(|&neighbor| labels[neighbor] > 0 || sums[neighbor] > 2);
+            if removable {
+                labels[vertex] = 0;
+                for &neighbor in graph.get_neighbors(vertex) {
+                    sums[neighbor] -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// Dominação romana com pesos nos vértices.
+pub struct WeightedRoman {
+    pub weights: Vec<usize>,
+}
+
+impl WeightedRoman {
+    pub fn from_file(path: &Path, graph: &Graph) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "Falha ao ler os pesos dos vértices '{}': {e}",
+                path.display()
+            )
+        })?;
+        let weights = content
+            .split_whitespace()
+            .map(|token| {
+                token
+                    .parse()
+                    .map_err(|_| format!("Peso inválido '{token}' em '{}'", path.display()))
+            })
+            .collect::<Result<Vec<usize>, String>>()?;
+        if weights.len() != graph.get_num_vertices() {
+            return Err(format!(
+                "'{}' tem {} pesos para um grafo com {} vértices",
+                path.display(),
+                weights.len(),
+                graph.get_num_vertices()
+            ));
+        }
+        Ok(Self { weights })
+    }
+}
+
+impl RomanVariant for WeightedRoman {
+    fn problem(&self) -> Problem {
+        Problem::WeightedRoman
+    }
+
+    fn max_label(&self) -> u8 {
+        2
+    }
+
+    fn violations(&self, graph: &Graph, labels: &[u8]) -> Vec<usize> {
+        graph.undominated_vertices(labels)
+    }
+
+    fn repair(&self, graph: &Graph, labels: &mut [u8]) {
+        ga::repair(graph, labels);
+    }
+
+    fn cost(&self, labels: &[u8]) -> usize {
+        labels
+            .iter()
+            .zip(&self.weights)
+            .map(|(&label, &weight)| usize::from(label) * weight)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{exact, graph::generators};
+
+    fn path(n: usize) -> Graph {
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        Graph::new(n, &edges)
+    }
+
+    fn cycle(n: usize) -> Graph {
+        let mut edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        edges.push((n - 1, 0));
+        Graph::new(n, &edges)
+    }
+
+    fn star(leaves: usize) -> Graph {
+        let edges: Vec<(usize, usize)> = (1..=leaves).map(|leaf| (0, leaf)).collect();
+        Graph::new(leaves + 1, &edges)
+    }
+
+    /// Grafos pequenos sem vértices isolados, onde todas as variantes estão definidas.
+    fn small_graphs() -> Vec<Graph> {
+        let mut graphs = vec![path(2), path(5), cycle(6), star(4)];
+        graphs.extend(
+            (0..10)
+                .map(|seed| generators::erdos_renyi(10, 0.3, seed))
+                .filter(|graph| (0..10).all(|v| graph.get_vertex_degree(v) > 0)),
+        );
+        graphs
+    }
+
+    fn variants(graph: &Graph) -> Vec<Box<dyn RomanVariant>> {
+        let weights = (0..graph.get_num_vertices()).map(|v| v % 3 + 1).collect();
+        vec![
+            Box::new(Roman),
+            Box::new(DoubleRoman),
+            Box::new(TotalRoman),
+            Box::new(Italian),
+            Box::new(WeightedRoman { weights }),
+        ]
+    }
+
+    #[test]
+    fn repair_makes_any_labeling_feasible() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for graph in small_graphs() {
+            let n = graph.get_num_vertices();
+            for variant in variants(&graph) {
+                let max_label = variant.max_label();
+                for _ in 0..50 {
+                    let mut labels: Vec<u8> =
+                        (0..n).map(|_| rng.random_range(0..=max_label)).collect();
+                    variant.repair(&graph, &mut labels);
+                    assert!(
+                        variant.is_feasible(&graph, &labels),
+                        "{}: {labels:?}",
+                        variant.problem()
+                    );
+                }
+                let mut zeros = vec![0; n];
+                variant.repair(&graph, &mut zeros);
+                assert!(variant.is_feasible(&graph, &zeros), "{}", variant.problem());
+            }
+        }
+    }
+
+    #[test]
+    fn lift_roman_keeps_feasibility() {
+        for graph in small_graphs() {
+            let optimal = exact::solve(&graph, std::time::Duration::from_secs(10)).solution;
+            for roman in [graph.h1(), graph.h2(), optimal] {
+                assert!(graph.is_roman_dominating(&roman));
+                for variant in variants(&graph) {
+                    let lifted = variant.lift_roman(&graph, roman.clone());
+                    assert!(
+                        variant.is_feasible(&graph, &lifted),
+                        "{}: {roman:?} -> {lifted:?}",
+                        variant.problem()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn double_roman_feasibility() {
+        let graph = path(3);
+        assert!(DoubleRoman.is_feasible(&graph, &[0, 3, 0]));
+        assert!(DoubleRoman.is_feasible(&graph, &[1, 2, 1]));
+        assert!(DoubleRoman.is_feasible(&graph, &[2, 0, 2]));
+        // Um 0 com um único vizinho 2, e um 1 sem vizinho com rótulo 2 ou mais.
+        assert_eq!(DoubleRoman.violations(&graph, &[0, 2, 0]), vec![0, 2]);
+        assert_eq!(DoubleRoman.violations(&graph, &[1, 1, 3]), vec![0]);
+        assert!(!DoubleRoman.is_feasible(&graph, &[0, 4, 0]));
+    }
+
+    #[test]
+    fn total_roman_feasibility() {
+        let graph = path(3);
+        assert!(TotalRoman.is_feasible(&graph, &[0, 2, 1]));
+        assert!(TotalRoman.is_feasible(&graph, &[1, 1, 1]));
+        // O 2 do centro não tem vizinho positivo.
+        assert_eq!(TotalRoman.violations(&graph, &[0, 2, 0]), vec![1]);
+        assert!(!TotalRoman.is_feasible(&graph, &[0, 0, 2]));
+
+        let mut labels = vec![0, 2, 0];
+        TotalRoman.repair(&graph, &mut labels);
+        assert!(TotalRoman.is_feasible(&graph, &labels));
+        assert_eq!(TotalRoman.cost(&labels), 3);
+    }
+
+    #[test]
+    fn total_roman_rejects_isolated_vertices() {
+        let graph = Graph::new(3, &[(0, 1)]);
+        let error = load(Problem::TotalRoman, &graph, Path::new("g.txt"))
+            .err()
+            .unwrap();
+        assert!(error.contains("vértice 2"), "{error}");
+    }
+
+    #[test]
+    fn italian_feasibility() {
+        let graph = cycle(4);
+        assert!(Italian.is_feasible(&graph, &[1, 0, 1, 0]));
+        assert!(Italian.is_feasible(&graph, &[2, 0, 1, 0]));
+        assert!(!Italian.is_feasible(&graph, &[2, 0, 0, 1]));
+        assert_eq!(Italian.violations(&graph, &[1, 0, 0, 0]), vec![1, 2, 3]);
+
+        // O reparo devolve os 1 dispensáveis a 0.
+        let mut labels = vec![1; 4];
+        Italian.repair(&graph, &mut labels);
+        assert!(Italian.is_feasible(&graph, &labels));
+        assert_eq!(Italian.cost(&labels), 2);
+    }
+
+    #[test]
+    fn weighted_roman_cost_and_feasibility() {
+        let graph = star(3);
+        let variant = WeightedRoman {
+            weights: vec![5, 1, 1, 1],
+        };
+        assert!(variant.is_feasible(&graph, &[2, 0, 0, 0]));
+        assert!(variant.is_feasible(&graph, &[0, 1, 2, 1]));
+        assert!(!variant.is_feasible(&graph, &[0, 1, 1, 1]));
+        assert_eq!(variant.cost(&[2, 0, 0, 0]), 10);
+        assert_eq!(variant.cost(&[0, 1, 2, 1]), 4);
+    }
+
+    #[test]
+    fn weighted_roman_reads_one_weight_per_vertex() {
+        let graph = path(3);
+        let path = std::env::temp_dir().join(format!("kambo-hive-{}.weights", std::process::id()));
+        fs::write(&path, "3 1\n2\n").unwrap();
+        assert_eq!(
+            WeightedRoman::from_file(&path, &graph).unwrap().weights,
+            vec![3, 1, 2]
+        );
+        fs::write(&path, "3 1").unwrap();
+        let error = WeightedRoman::from_file(&path, &graph).err().unwrap();
+        assert!(
+            error.contains("2 pesos para um grafo com 3 vértices"),
+            "{error}"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}